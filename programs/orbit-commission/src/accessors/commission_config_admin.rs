@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionConfig,
+    CommissionMarketErrors
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// CONFIG (multisig only)
+
+#[derive(Accounts)]
+pub struct InitCommissionConfig<'info>{
+    #[account(
+        init,
+        space = 100,
+        payer = payer,
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Account<'info, CommissionConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateCommissionConfig<'info>{
+    #[account(
+        mut,
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Account<'info, CommissionConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+fn write_config(config: &mut CommissionConfig, fee_bps: u16, referral_bps: u16, discount_rate: u8, treasury: Pubkey, tip_fee_bps: u16, multisig_wallet: Pubkey) -> Result<()>{
+    // a zero fee would leave no cut to pay referrals out of
+    if fee_bps == 0 || fee_bps > 10000 || (referral_bps as u32) * 2 > fee_bps as u32 || discount_rate > 100 || tip_fee_bps > 10000{
+        return err!(CommissionMarketErrors::InvalidConfig)
+    }
+    config.fee_bps = fee_bps;
+    config.referral_bps = referral_bps;
+    config.discount_rate = discount_rate;
+    config.treasury = treasury;
+    config.tip_fee_bps = tip_fee_bps;
+    config.multisig_wallet = multisig_wallet;
+    Ok(())
+}
+
+pub fn init_commission_config_handler(ctx: Context<InitCommissionConfig>, fee_bps: u16, referral_bps: u16, discount_rate: u8, treasury: Pubkey, tip_fee_bps: u16, multisig_wallet: Pubkey) -> Result<()>{
+    write_config(&mut ctx.accounts.commission_config, fee_bps, referral_bps, discount_rate, treasury, tip_fee_bps, multisig_wallet)
+}
+
+pub fn update_commission_config_handler(ctx: Context<UpdateCommissionConfig>, fee_bps: u16, referral_bps: u16, discount_rate: u8, treasury: Pubkey, tip_fee_bps: u16, multisig_wallet: Pubkey) -> Result<()>{
+    write_config(&mut ctx.accounts.commission_config, fee_bps, referral_bps, discount_rate, treasury, tip_fee_bps, multisig_wallet)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn config() -> CommissionConfig{
+        CommissionConfig{
+            fee_bps: 0,
+            referral_bps: 0,
+            discount_rate: 0,
+            treasury: Pubkey::default(),
+            tip_fee_bps: 0,
+            multisig_wallet: Pubkey::default()
+        }
+    }
+
+    #[test]
+    fn writes_valid_configs(){
+        let mut c = config();
+        let treasury = Pubkey::new_unique();
+        let multisig_wallet = Pubkey::new_unique();
+        write_config(&mut c, 500, 250, 95, treasury, 10000, multisig_wallet).unwrap();
+        assert_eq!((c.fee_bps, c.referral_bps, c.discount_rate, c.tip_fee_bps), (500, 250, 95, 10000));
+        assert_eq!((c.treasury, c.multisig_wallet), (treasury, multisig_wallet));
+    }
+
+    #[test]
+    fn fee_has_to_be_positive_and_at_most_the_price(){
+        let mut c = config();
+        assert!(write_config(&mut c, 0, 0, 95, Pubkey::default(), 0, Pubkey::default()).is_err());
+        assert!(write_config(&mut c, 10001, 0, 95, Pubkey::default(), 0, Pubkey::default()).is_err());
+        write_config(&mut c, 10000, 0, 95, Pubkey::default(), 0, Pubkey::default()).unwrap();
+    }
+
+    #[test]
+    fn both_referral_halves_fit_in_the_fee(){
+        let mut c = config();
+        assert!(write_config(&mut c, 500, 251, 95, Pubkey::default(), 0, Pubkey::default()).is_err());
+        assert!(write_config(&mut c, 500, u16::MAX, 95, Pubkey::default(), 0, Pubkey::default()).is_err());
+        assert_eq!(c.referral_bps, 0);
+    }
+
+    #[test]
+    fn rejects_out_of_range_discounts_and_tip_fees(){
+        let mut c = config();
+        assert!(write_config(&mut c, 500, 0, 101, Pubkey::default(), 0, Pubkey::default()).is_err());
+        assert!(write_config(&mut c, 500, 0, 95, Pubkey::default(), 10001, Pubkey::default()).is_err());
+    }
+}
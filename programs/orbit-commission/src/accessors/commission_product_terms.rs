@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use orbit_product::CommissionProduct;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    id,
+    CommissionProductTerms,
+    CurrencyPrice,
+    AddOn,
+    CommissionMarketErrors,
+    PRODUCT_TERMS_SPACE,
+    MAX_ACCEPTED_CURRENCIES,
+    MAX_ADD_ONS,
+    DELIVERY_WINDOW
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// PRODUCT TERMS (seller)
+
+#[derive(Accounts)]
+pub struct InitProductTerms<'info>{
+    #[account(
+        init,
+        space = PRODUCT_TERMS_SPACE,
+        payer = seller_wallet,
+        seeds = [
+            b"commission_product_terms",
+            commission_product.key().as_ref()
+        ],
+        bump
+    )]
+    pub product_terms: Account<'info, CommissionProductTerms>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateProductTerms<'info>{
+    #[account(
+        mut,
+        seeds = [
+            b"commission_product_terms",
+            commission_product.key().as_ref()
+        ],
+        bump
+    )]
+    pub product_terms: Account<'info, CommissionProductTerms>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+}
+
+fn write_terms(terms: &mut CommissionProductTerms, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>) -> Result<()>{
+    if (min_price > base_price) || (currency_prices.len() > MAX_ACCEPTED_CURRENCIES){
+        return err!(CommissionMarketErrors::InvalidProductTerms)
+    }
+    for (i, listed) in currency_prices.iter().enumerate(){
+        if (listed.price == 0) || currency_prices[..i].iter().any(|other| other.mint == listed.mint){
+            return err!(CommissionMarketErrors::InvalidProductTerms)
+        }
+    }
+    // every rush add-on together still has to leave the seller some time
+    let rush = add_ons.iter().fold(0i64, |total, add_on| total.saturating_add(add_on.delivery_delta.min(0)));
+    if (add_ons.len() > MAX_ADD_ONS) || (DELIVERY_WINDOW + rush <= 0){
+        return err!(CommissionMarketErrors::InvalidProductTerms)
+    }
+    for (i, add_on) in add_ons.iter().enumerate(){
+        if add_ons[..i].iter().any(|other| other.id == add_on.id){
+            return err!(CommissionMarketErrors::InvalidProductTerms)
+        }
+    }
+    terms.free_revisions = free_revisions;
+    terms.base_price = base_price;
+    terms.min_price = min_price;
+    terms.currency_prices = currency_prices;
+    terms.instant_accept = instant_accept;
+    terms.add_ons = add_ons;
+    Ok(())
+}
+
+pub fn init_product_terms_handler(ctx: Context<InitProductTerms>, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>) -> Result<()>{
+    ctx.accounts.product_terms.product = ctx.accounts.commission_product.key();
+    write_terms(&mut ctx.accounts.product_terms, free_revisions, base_price, min_price, currency_prices, instant_accept, add_ons)
+}
+
+pub fn update_product_terms_handler(ctx: Context<UpdateProductTerms>, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>) -> Result<()>{
+    write_terms(&mut ctx.accounts.product_terms, free_revisions, base_price, min_price, currency_prices, instant_accept, add_ons)
+}
+
+/// terms for a product, None if the seller never set any
+pub fn load_product_terms(product_terms: &AccountInfo) -> Result<Option<CommissionProductTerms>>{
+    if product_terms.owner != &id(){
+        return Ok(None)
+    }
+    let data = product_terms.try_borrow_data()?;
+    Ok(Some(CommissionProductTerms::try_deserialize(&mut &data[..])?))
+}
+
+/// free revisions for a product. products the seller never set terms for get none
+pub fn product_free_revisions(product_terms: &AccountInfo) -> Result<u8>{
+    Ok(load_product_terms(product_terms)?.map_or(0, |terms| terms.free_revisions))
+}
+
+/// buyer opened transactions have to respect the listing. products listed per currency
+/// only take those currencies, each at exactly its listed price. otherwise any currency goes and the
+/// floor is min_price when the seller allows haggling, the base price if not.
+/// quotes skip this, the seller already signed off on their price
+pub fn check_listing(terms: &Option<CommissionProductTerms>, price: u64, currency: &Pubkey) -> Result<()>{
+    let terms = match terms{
+        Some(terms) => terms,
+        None => return Ok(())
+    };
+    if !terms.currency_prices.is_empty(){
+        return match terms.currency_prices.iter().find(|listed| listed.mint == *currency){
+            Some(listed) if listed.price == price => Ok(()),
+            Some(_) => err!(CommissionMarketErrors::PriceNotListed),
+            None => err!(CommissionMarketErrors::CurrencyNotAccepted)
+        }
+    }
+    let floor = if terms.min_price > 0 {terms.min_price} else {terms.base_price};
+    if price < floor{
+        return err!(CommissionMarketErrors::PriceBelowListing)
+    }
+    Ok(())
+}
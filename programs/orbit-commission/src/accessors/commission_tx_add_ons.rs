@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionTransaction,
+    CommissionProductTerms,
+    CommissionMarketErrors,
+    OrderedAddOn,
+    AddOnState,
+    DELIVERY_WINDOW
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// ADD-ONS
+/// buyers pick extras off the product's add-on list when opening, instant accept included.
+/// the seller confirms or rejects each one on accept and only what they agreed to is escrowed
+
+fn order_add_ons(terms: &Option<CommissionProductTerms>, ids: &[u8]) -> Result<Vec<OrderedAddOn>>{
+    let listed = terms.as_ref().map_or(&[][..], |terms| &terms.add_ons[..]);
+    let mut ordered: Vec<OrderedAddOn> = Vec::with_capacity(ids.len());
+    for id in ids{
+        let add_on = match listed.iter().find(|add_on| add_on.id == *id){
+            Some(add_on) if !ordered.iter().any(|other| other.id == *id) => add_on,
+            _ => return err!(CommissionMarketErrors::AddOnNotListed)
+        };
+        ordered.push(OrderedAddOn{
+            id: *id,
+            price: add_on.price,
+            delivery_delta: add_on.delivery_delta,
+            state: AddOnState::Pending
+        });
+    }
+    Ok(ordered)
+}
+
+/// the opening price and the add-ons to put on the transaction. once anything is added the
+/// price is the product's listed price in `currency` plus every add-on, not what the buyer passed
+pub fn add_to_price(terms: &Option<CommissionProductTerms>, currency: &Pubkey, price: u64, add_on_ids: &[u8]) -> Result<(u64, Vec<OrderedAddOn>)>{
+    let ordered = order_add_ons(terms, add_on_ids)?;
+    let terms = match terms{
+        Some(terms) if !ordered.is_empty() => terms,
+        _ => return Ok((price, ordered))
+    };
+    let listed = terms.currency_prices.iter()
+        .find(|listed| listed.mint == *currency)
+        .map_or(terms.base_price, |listed| listed.price);
+    match ordered.iter().try_fold(listed, |total, add_on| total.checked_add(add_on.price)){
+        Some(total) => Ok((total, ordered)),
+        None => err!(CommissionMarketErrors::PriceOverflow)
+    }
+}
+
+/// a dispute discount used at open covers the add-ons too, so rejecting one refunds its
+/// discounted price
+pub fn discount_price(price: u64, add_ons: &mut [OrderedAddOn], discount_rate: u8) -> Result<u64>{
+    let discounted = |price: u64| match price.checked_mul(discount_rate as u64){
+        Some(scaled) => Ok(scaled / 100),
+        None => err!(CommissionMarketErrors::PriceOverflow)
+    };
+    for add_on in add_ons.iter_mut(){
+        add_on.price = discounted(add_on.price)?;
+    }
+    discounted(price)
+}
+
+/// SELLER ACCEPT
+
+/// anything the seller does not reject is accepted. rejected add-ons come off the price
+/// before the buyer funds, so the escrow only ever holds what the seller agreed to
+pub fn review_add_ons(tx: &mut CommissionTransaction, rejected: &[u8]) -> Result<()>{
+    if rejected.iter().any(|id| !tx.add_ons.iter().any(|add_on| add_on.id == *id)){
+        return err!(CommissionMarketErrors::AddOnNotListed)
+    }
+    for add_on in tx.add_ons.iter_mut(){
+        if rejected.contains(&add_on.id){
+            add_on.state = AddOnState::Rejected;
+            tx.metadata.transaction_price -= add_on.price;
+        }else{
+            add_on.state = AddOnState::Accepted;
+        }
+    }
+    Ok(())
+}
+
+/// delivery window from funding, moved by every accepted add-on
+pub fn delivery_window(tx: &CommissionTransaction) -> i64{
+    tx.add_ons.iter()
+        .filter(|add_on| add_on.state == AddOnState::Accepted)
+        .fold(DELIVERY_WINDOW, |window, add_on| window.saturating_add(add_on.delivery_delta))
+}
@@ -0,0 +1,740 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::{
+    transaction_struct::TransactionState,
+    program::OrbitTransaction,
+    BuyerOpenTransactions,
+    SellerOpenTransactions
+};
+use market_accounts::{
+    OrbitMarketAccount,
+    program::OrbitMarketAccounts
+};
+use crate::{
+    CommissionTransaction,
+    CommissionConfig,
+    CommissionMarketErrors,
+    CancelOffer,
+    DisputeState,
+    BPS_DENOMINATOR,
+    program::OrbitCommissionMarket,
+    Payout,
+    split_escrow,
+    ensure_bps_rates,
+    fit_transaction,
+    refund_seller_rent,
+    RentPayer,
+    CommissionTransition,
+    emit_transition,
+    record_outcome,
+    CommissionOutcome,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// BUYER CANCEL
+/// the buyer can walk away until they fund. nothing is in escrow yet, so this only
+/// unwinds what open did: logs, the dispute discount and the account rent
+
+#[allow(clippy::too_many_arguments)]
+fn unwind_open<'info>(
+    tx: &CommissionTransaction,
+    transaction_program: AccountInfo<'info>,
+    market_account_program: AccountInfo<'info>,
+    commission_program: AccountInfo<'info>,
+    commission_auth: AccountInfo<'info>,
+    buyer_account: AccountInfo<'info>,
+    buyer_log: AccountInfo<'info>,
+    seller_log: AccountInfo<'info>,
+    auth_bump: u8
+) -> Result<()>{
+    if tx.used_discount{
+        market_accounts::cpi::increment_dispute_discounts(
+            CpiContext::new_with_signer(
+                market_account_program,
+                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                    market_account: buyer_account,
+                    caller_auth: commission_auth.clone(),
+                    caller: commission_program.clone()
+                },
+                &[&[b"market_authority", &[auth_bump]]]
+            )
+        )?;
+    }
+
+    clear_logs(tx, transaction_program, commission_program, commission_auth, buyer_log, seller_log, auth_bump)
+}
+
+fn clear_logs<'info>(
+    tx: &CommissionTransaction,
+    transaction_program: AccountInfo<'info>,
+    commission_program: AccountInfo<'info>,
+    commission_auth: AccountInfo<'info>,
+    buyer_log: AccountInfo<'info>,
+    seller_log: AccountInfo<'info>,
+    auth_bump: u8
+) -> Result<()>{
+    orbit_transaction::cpi::clear_seller_commissions_transaction(
+        CpiContext::new_with_signer(
+            transaction_program.clone(),
+            orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
+                transactions_log: seller_log,
+                caller_auth: commission_auth.clone(),
+                caller: commission_program.clone()
+            },
+            &[&[b"market_authority", &[auth_bump]]]
+        ),
+        tx.metadata.seller_tx_index
+    )?;
+
+    orbit_transaction::cpi::clear_buyer_commissions_transaction(
+        CpiContext::new_with_signer(
+            transaction_program,
+            orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
+                transactions_log: buyer_log,
+                caller_auth: commission_auth,
+                caller: commission_program
+            },
+            &[&[b"market_authority", &[auth_bump]]]
+        ),
+        tx.metadata.seller_tx_index
+    )
+}
+
+/// SOL
+
+#[derive(Accounts)]
+pub struct BuyerCancelSol<'info>{
+    #[account(
+        mut,
+        close = buyer_wallet,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::Opened) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed),
+        constraint = !commission_transaction.metadata.funded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    /// CPI AND EXTRANEOUS
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+}
+
+pub fn buyer_cancel_sol_handler(ctx: Context<BuyerCancelSol>) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    unwind_open(
+        &ctx.accounts.commission_transaction,
+        ctx.accounts.transaction_program.to_account_info(),
+        ctx.accounts.market_account_program.to_account_info(),
+        ctx.accounts.commission_program.to_account_info(),
+        ctx.accounts.commission_auth.to_account_info(),
+        ctx.accounts.buyer_account.to_account_info(),
+        ctx.accounts.buyer_transactions_log.to_account_info(),
+        ctx.accounts.seller_transactions_log.to_account_info(),
+        *auth_bump
+    )?;
+    // any rent the seller put in since accepting goes back to them, the close takes the rest
+    refund_seller_rent(&ctx.accounts.commission_transaction, &ctx.accounts.seller_wallet.to_account_info())?;
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::BuyerCancelled)?;
+    Ok(())
+}
+
+/// SPL
+
+#[derive(Accounts)]
+pub struct BuyerCancelSpl<'info>{
+    #[account(
+        mut,
+        close = buyer_wallet,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::Opened) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed),
+        constraint = !commission_transaction.metadata.funded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: escrow token account opened with the transaction, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    /// CPI AND EXTRANEOUS
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub fn buyer_cancel_spl_handler(ctx: Context<BuyerCancelSpl>) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    unwind_open(
+        &ctx.accounts.commission_transaction,
+        ctx.accounts.transaction_program.to_account_info(),
+        ctx.accounts.market_account_program.to_account_info(),
+        ctx.accounts.commission_program.to_account_info(),
+        ctx.accounts.commission_auth.to_account_info(),
+        ctx.accounts.buyer_account.to_account_info(),
+        ctx.accounts.buyer_transactions_log.to_account_info(),
+        ctx.accounts.seller_transactions_log.to_account_info(),
+        *auth_bump
+    )?;
+    // the escrow was created and paid for by the buyer at open
+    token_interface::close_escrow(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.escrow_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        ctx.accounts.commission_auth.to_account_info(),
+        &[&[b"market_authority", &[*auth_bump]]]
+    )?;
+    refund_seller_rent(&ctx.accounts.commission_transaction, &ctx.accounts.seller_wallet.to_account_info())?;
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::BuyerCancelled)?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// MUTUAL CANCEL
+/// either side offers a split of what is still escrowed, the other side accepts it
+/// and the escrow is paid out right away. the platform fee only touches the seller's part
+
+#[derive(Accounts)]
+pub struct ProposeMutualCancel<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Shipped),
+        constraint = commission_transaction.dispute_state != DisputeState::Open
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        has_one = wallet,
+        constraint =
+        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
+        (proposer_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub proposer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// a new offer from either side replaces the old one
+pub fn propose_mutual_cancel_handler(ctx: Context<ProposeMutualCancel>, seller_bps: u16) -> Result<()>{
+    if seller_bps > BPS_DENOMINATOR{
+        return err!(CommissionMarketErrors::InvalidRate)
+    }
+    ctx.accounts.commission_transaction.cancel_offer = Some(CancelOffer{
+        proposer: ctx.accounts.proposer_account.voter_id,
+        seller_bps
+    });
+    let rent_payer = RentPayer::of(&ctx.accounts.commission_transaction, ctx.accounts.proposer_account.voter_id);
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.wallet.to_account_info(),
+        rent_payer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MutualCancelProposed{seller_bps})?;
+    Ok(())
+}
+
+/// checks the acceptor is the other side of the offer and returns the payout.
+/// `seller_bps` has to repeat the offer so a last second re-proposal can't be accepted blind
+fn settle_cancel(tx: &mut CommissionTransaction, acceptor: u64, seller_bps: u16) -> Result<Payout>{
+    let offer = match tx.cancel_offer{
+        Some(offer) if offer.seller_bps == seller_bps => offer,
+        _ => return err!(CommissionMarketErrors::NoCancelOffer)
+    };
+    if offer.proposer == acceptor{
+        return err!(CommissionMarketErrors::InvalidCancelAcceptor)
+    }
+    ensure_bps_rates(tx);
+
+    // the offer splits the escrow, then the fee comes out of the seller's part
+    let share = split_escrow(tx.metadata.transaction_price - tx.milestones_released, offer.seller_bps, false, 0, false);
+    let seller = split_escrow(share.seller, tx.rate_bps, true, 0, false);
+
+    tx.cancel_offer = None;
+    tx.metadata.transaction_state = TransactionState::Closed;
+    record_outcome(tx, CommissionOutcome::Cancelled)?;
+    Ok(Payout{
+        buyer: share.buyer,
+        ..seller
+    })
+}
+
+/// SOL
+
+#[derive(Accounts)]
+pub struct MutualCancelSol<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Shipped),
+        constraint = commission_transaction.dispute_state != DisputeState::Open
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
+
+    /// BUYER
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    /// whichever side did not make the offer
+    #[account(
+        constraint = (acceptor_wallet.key() == buyer_account.wallet) || (acceptor_wallet.key() == seller_account.wallet)
+    )]
+    pub acceptor_wallet: Signer<'info>,
+
+    /// CPI AND EXTRANEOUS
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    #[account(
+        mut,
+        address = commission_config.treasury
+    )]
+    pub treasury_wallet: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+}
+
+pub fn accept_mutual_cancel_sol_handler(ctx: Context<MutualCancelSol>, seller_bps: u16) -> Result<()>{
+    let acceptor = if ctx.accounts.acceptor_wallet.key() == ctx.accounts.buyer_account.wallet{
+        ctx.accounts.buyer_account.voter_id
+    }else{
+        ctx.accounts.seller_account.voter_id
+    };
+    let payout = settle_cancel(&mut ctx.accounts.commission_transaction, acceptor, seller_bps)?;
+
+    let comm_tx = ctx.accounts.commission_transaction.key();
+    let comm_seed = comm_tx.as_ref();
+    let buyer_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_log.as_ref();
+
+    if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+        orbit_transaction::close_escrow_sol_flat!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.seller_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+            payout.seller
+        ).expect("could not pay seller");
+        orbit_transaction::close_escrow_sol_flat!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.treasury_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+            payout.platform
+        ).expect("could not pay fee");
+        // payout.buyer plus the escrow rent
+        orbit_transaction::close_escrow_sol_rate!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+            100
+        ).expect("could not refund buyer");
+    }else{
+        return err!(CommissionMarketErrors::InvalidEscrowBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+        clear_logs(
+            &ctx.accounts.commission_transaction,
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.commission_program.to_account_info(),
+            ctx.accounts.commission_auth.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            *auth_bump
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MutualCancelled{seller: payout.seller, buyer: payout.buyer})?;
+    Ok(())
+}
+
+/// SPL
+
+#[derive(Accounts)]
+pub struct MutualCancelSpl<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Shipped),
+        constraint = commission_transaction.dispute_state != DisputeState::Open
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    /// BUYER
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&seller_token_account, &commission_transaction.metadata.currency, &seller_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub seller_token_account: UncheckedAccount<'info>,
+
+    /// whichever side did not make the offer
+    #[account(
+        constraint = (acceptor_wallet.key() == buyer_account.wallet) || (acceptor_wallet.key() == seller_account.wallet)
+    )]
+    pub acceptor_wallet: Signer<'info>,
+
+    /// CPI AND EXTRANEOUS
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    /// CHECK: treasury token account of the transaction currency
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&treasury_ata, &commission_transaction.metadata.currency, &commission_config.treasury) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub treasury_ata: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub fn accept_mutual_cancel_spl_handler(ctx: Context<MutualCancelSpl>, seller_bps: u16) -> Result<()>{
+    let acceptor = if ctx.accounts.acceptor_wallet.key() == ctx.accounts.buyer_account.wallet{
+        ctx.accounts.buyer_account.voter_id
+    }else{
+        ctx.accounts.seller_account.voter_id
+    };
+    let payout = settle_cancel(&mut ctx.accounts.commission_transaction, acceptor, seller_bps)?;
+
+    if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+        for (to, amt) in [
+            (ctx.accounts.seller_token_account.to_account_info(), payout.seller),
+            (ctx.accounts.treasury_ata.to_account_info(), payout.platform)
+        ]{
+            token_interface::transfer_checked(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                to,
+                ctx.accounts.commission_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                token_interface::gross_up(&ctx.accounts.token_mint.to_account_info(), amt)?
+            ).expect("could not pay out escrow");
+        }
+        // payout.buyer plus whatever the funding reserve did not spend on transfer fees
+        let buyer_amt = token_interface::amount(&ctx.accounts.escrow_account.to_account_info())?;
+        token_interface::transfer_checked(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.commission_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            buyer_amt
+        ).expect("could not refund buyer");
+        // same as closing: the rent goes back to the buyer unless token-2022 is still withholding fees
+        if token_interface::withheld_fees(&ctx.accounts.escrow_account.to_account_info())? == 0{
+            token_interface::close_escrow(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_wallet.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]]
+            )?;
+        }
+        clear_logs(
+            &ctx.accounts.commission_transaction,
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.commission_program.to_account_info(),
+            ctx.accounts.commission_auth.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            *auth_bump
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MutualCancelled{seller: payout.seller, buyer: payout.buyer})?;
+    Ok(())
+}
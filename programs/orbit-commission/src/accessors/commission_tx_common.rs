@@ -1,1057 +1,1425 @@
-use anchor_lang::{
-    prelude::*,
-    AccountsClose,
-    solana_program::{
-        program::{
-            invoke,
-            invoke_signed
-        },
-        system_instruction::transfer
-    }
-};
-use orbit_transaction::{
-    transaction_trait::OrbitTransactionTrait,
-    transaction_struct::TransactionState,
-    TransactionReviews,
-    BuyerOpenTransactions,
-    TransactionErrors, SellerOpenTransactions
-};
-use market_accounts::{
-    market_account::OrbitMarketAccount, program::OrbitMarketAccounts,
-    structs::market_account_trait::OrbitMarketAccountTrait,
-    MarketAccountErrors,
-    ReviewErrors,
-    OrbitReflink
-};
-use anchor_spl::token::{
-    accessor::amount,
-    TokenAccount
-};
-use crate::{
-    id,
-    CommissionTransaction,
-
-    CommissionMarketErrors,
-    OpenCommissionTransactionSol,
-    OpenCommissionTransactionSpl,
-    CloseCommissionTransactionSol,
-    CloseCommissionTransactionSpl,
-    FundEscrowSol,
-    FundEscrowSpl,
-    BuyerDecisionState, program::OrbitCommissionMarket, SellerEarlyDeclineSpl, SellerEarlyDeclineSol
-};
-
-////////////////////////////////////////////////////////////////////
-/// ORBIT BASE TRANSACTION FUNCTIONALITIES
-
-#[derive(Accounts)]
-pub struct CloseTransactionAccount<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::Closed,
-    )]
-    pub commission_transaction: Account<'info, CommissionTransaction>,
-
-    #[account(
-        has_one = wallet,
-        constraint = 
-        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
-        (proposer_account.voter_id == commission_transaction.metadata.buyer)
-    )]
-    pub proposer_account: Account<'info, OrbitMarketAccount>,
-    
-    pub wallet: Signer<'info>,
-
-    #[account(
-        constraint = {buyer_account.voter_id == commission_transaction.metadata.buyer}
-    )]
-    pub buyer_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut
-    )]
-    pub buyer_wallet: SystemAccount<'info>
-}
-
-impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, OpenCommissionTransactionSol<'a>, OpenCommissionTransactionSpl<'b>, CloseCommissionTransactionSol<'c>, CloseCommissionTransactionSpl<'d>, FundEscrowSol<'e>, FundEscrowSpl<'f>, CloseTransactionAccount<'g>, SellerEarlyDeclineSol<'h>, SellerEarlyDeclineSpl<'i>> for CommissionTransaction{
-    fn open_sol(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool) -> Result<()>{
-        let auth_bump: &u8;
-        if let Some(ab) = ctx.bumps.get("commission_auth"){
-            auth_bump = ab
-        }else{
-            return err!(CommissionMarketErrors::InvalidAuthBump)
-        };
-        if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
-            ctx.accounts.commission_transaction.metadata.rate = 100;
-            price = price * 95 / 100;
-            
-            market_accounts::cpi::decrement_dispute_discounts(
-                CpiContext::new_with_signer(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_market_account.to_account_info(),
-                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                        caller: ctx.accounts.commission_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-            )?;
-        }else{
-            ctx.accounts.commission_transaction.metadata.rate = 95
-        }
-        ctx.accounts.commission_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
-        ctx.accounts.commission_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
-        ctx.accounts.commission_transaction.metadata.product = ctx.accounts.commission_product.metadata.index;
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Opened;
-        ctx.accounts.commission_transaction.metadata.transaction_price = price;
-        ctx.accounts.commission_transaction.metadata.funded = false;
-        ctx.accounts.commission_transaction.metadata.currency = System::id();
-
-        ctx.accounts.commission_transaction.num_keys = 0;
-        ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Null;
-
-        ctx.accounts.commission_transaction.metadata.reviews = TransactionReviews{
-            buyer: false,
-            seller: false
-        };
-
-        orbit_transaction::cpi::add_buyer_commissions_transaction(
-            CpiContext::new_with_signer(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddBuyerCommissionsTransactions{
-                    buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
-                    wallet: ctx.accounts.buyer_wallet.to_account_info(),
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    tx: ctx.accounts.commission_transaction.to_account_info()
-                },
-                &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            buyer_index
-        )?;
-        orbit_transaction::cpi::add_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    tx: ctx.accounts.commission_transaction.to_account_info()
-                }
-            ),
-            seller_index
-        )?;
-
-        Ok(())
-    }
-
-    fn open_spl(ctx: Context<OpenCommissionTransactionSpl>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool) -> Result<()>{
-        let auth_bump: &u8;
-        if let Some(ab) = ctx.bumps.get("commission_auth"){
-            auth_bump = ab
-        }else{
-            return err!(CommissionMarketErrors::InvalidAuthBump)
-        };
-        if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
-            ctx.accounts.commission_transaction.metadata.rate = 100;
-            price = price * 95 / 100;
-            
-            market_accounts::cpi::decrement_dispute_discounts(
-                CpiContext::new_with_signer(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_market_account.to_account_info(),
-                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                        caller: ctx.accounts.commission_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-            )?;
-        }else{
-            ctx.accounts.commission_transaction.metadata.rate = 95
-        }
-        ctx.accounts.commission_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
-        ctx.accounts.commission_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
-        ctx.accounts.commission_transaction.metadata.product = ctx.accounts.commission_product.metadata.index;
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Opened;
-        ctx.accounts.commission_transaction.metadata.transaction_price = price;
-        ctx.accounts.commission_transaction.metadata.funded = false;
-        ctx.accounts.commission_transaction.metadata.currency = ctx.accounts.token_mint.key();
-        
-        ctx.accounts.commission_transaction.num_keys = 0;
-        ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Null;
-
-        ctx.accounts.commission_transaction.metadata.reviews = TransactionReviews{
-            buyer: false,
-            seller: false
-        };
-
-        orbit_transaction::cpi::add_buyer_commissions_transaction(
-            CpiContext::new_with_signer(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddBuyerCommissionsTransactions{
-                    buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
-                    wallet: ctx.accounts.buyer_wallet.to_account_info(),
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    tx: ctx.accounts.commission_transaction.to_account_info()
-                },
-                &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            buyer_index
-        )?;
-        orbit_transaction::cpi::add_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    tx: ctx.accounts.commission_transaction.to_account_info()
-                }
-            ),
-            seller_index
-        )?;
-        Ok(())
-    }
-
-    fn close_sol(ctx: Context<'_, '_, '_, 'c, CloseCommissionTransactionSol<'c>>) -> Result<()>{
-        let comm_tx = ctx.accounts.commission_transaction.key();
-        let comm_seed = comm_tx.as_ref();
-        let buyer_log = ctx.accounts.buyer_transactions_log.key();
-        let buyer_tx_log_seed = buyer_log.as_ref();
-
-        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
-            if (ctx.accounts.commission_transaction.close_rate == 95)
-                && (ctx.accounts.commission_transaction.final_decision == BuyerDecisionState::Accept){
-                    let bal = ctx.accounts.escrow_account.lamports();
-                    let mut residual_amt = bal * 5/100;
-                    if  (ctx.accounts.buyer_account.used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
-                        (ctx.remaining_accounts[0].key() == ctx.accounts.buyer_account.used_reflink)
-                    {
-                        let reflink_amt = bal * 25 / 10000;
-                        residual_amt = bal * 45/1000;
-                        orbit_transaction::close_escrow_sol_flat!(
-                            ctx.accounts.escrow_account.to_account_info(),
-                            ctx.accounts.buyer_wallet.to_account_info(),
-                            &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                            reflink_amt
-                        ).expect("couldnt close escrow");
-                        match orbit_transaction::remaining_accounts_to_wallet!(ctx.remaining_accounts){
-                            Ok(reflink_wallet) => {
-                                orbit_transaction::close_escrow_sol_flat!(
-                                    ctx.accounts.escrow_account.to_account_info(),
-                                    reflink_wallet.to_account_info(),
-                                    &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                                    reflink_amt
-                                ).expect("couldnt close escrow");
-                                reflink_wallet.exit(ctx.program_id)?;
-                            },
-                            Err(e) => return Err(e)
-                        }
-                    }
-                    orbit_transaction::close_escrow_sol_flat!(
-                        ctx.accounts.escrow_account.to_account_info(),
-                        ctx.accounts.multisig_wallet.to_account_info(),
-                        &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                        residual_amt
-                    ).expect("couldnt close escrow");
-                }
-
-                orbit_transaction::close_escrow_sol_rate!(
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.seller_wallet.to_account_info(),
-                    &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                    ctx.accounts.commission_transaction.close_rate
-                ).expect("could not transfer tokens");
-                orbit_transaction::close_escrow_sol_rate!(
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.buyer_wallet.to_account_info(),
-                    &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                    100
-                ).expect("could not transfer tokens");
-            
-            
-        }else{
-            return err!(CommissionMarketErrors::InvalidEscrowBump)
-        };
-
-        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
-            orbit_transaction::post_tx_incrementing!(
-                ctx.accounts.market_account_program.to_account_info(),
-                ctx.accounts.buyer_account.to_account_info(),
-                ctx.accounts.seller_account.to_account_info(),
-                ctx.accounts.commission_auth.to_account_info(),
-                ctx.accounts.commission_program.to_account_info(),
-                &[&[b"market_authority", &[*auth_bump]]]
-            )?;
-            orbit_product::cpi::commission_increment_times_sold(
-                CpiContext::new_with_signer(
-                    ctx.accounts.product_program.to_account_info(),
-                    orbit_product::cpi::accounts::UpdateCommissionQuantityInternal{
-                        product: ctx.accounts.commission_product.to_account_info(),
-                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                        caller: ctx.accounts.commission_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-            )?;
-        }else{
-            return err!(CommissionMarketErrors::InvalidAuthBump)
-        };
-
-        orbit_transaction::cpi::clear_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
-        Ok(())
-    }
-
-    fn close_spl(ctx: Context<'_, '_, '_, 'd, CloseCommissionTransactionSpl<'d>>) -> Result<()>{
-        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
-            if (ctx.accounts.commission_transaction.close_rate == 95)
-                && (ctx.accounts.commission_transaction.final_decision == BuyerDecisionState::Accept){
-                    let bal = amount(&ctx.accounts.escrow_account.to_account_info()).expect("could not deserialize token account");
-                    let mut residual_amt = bal * 5/100;
-                    if  (ctx.accounts.buyer_account.used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
-                        (ctx.remaining_accounts[0].key() == ctx.accounts.buyer_account.used_reflink)
-                    {
-                        let reflink_amt = bal * 25 / 10000;
-                        residual_amt = bal * 45/1000;
-                        orbit_transaction::close_escrow_spl_flat!(
-                            ctx.accounts.token_program.to_account_info(),
-                            ctx.accounts.escrow_account.to_account_info(),
-                            ctx.accounts.buyer_token_account.to_account_info(),
-                            ctx.accounts.commission_auth.to_account_info(),
-                            &[&[b"market_authority", &[*auth_bump]]],
-                            reflink_amt
-                        ).expect("couldnt close escrow");
-                        
-
-                        match orbit_transaction::remaining_accounts_to_token_account!(ctx.remaining_accounts){
-                            Ok(reflink_token_account) => {
-                                orbit_transaction::close_escrow_spl_flat!(
-                                    ctx.accounts.token_program.to_account_info(),
-                                    ctx.accounts.escrow_account.to_account_info(),
-                                    reflink_token_account.to_account_info(),
-                                    ctx.accounts.commission_auth.to_account_info(),
-                                    &[&[b"market_authority", &[*auth_bump]]],
-                                    reflink_amt
-                                ).expect("couldnt close escrow");
-                                reflink_token_account.exit(ctx.program_id)?;
-                            },
-                            Err(e) => return Err(e)
-                        }
-                    }
-                    orbit_transaction::close_escrow_spl_flat!(
-                        ctx.accounts.token_program.to_account_info(),
-                        ctx.accounts.escrow_account.to_account_info(),
-                        ctx.accounts.multisig_ata.to_account_info(),
-                        ctx.accounts.commission_auth.to_account_info(),
-                        &[&[b"market_authority", &[*auth_bump]]],
-                        residual_amt
-                    ).expect("couldnt close escrow");
-                }
-
-                orbit_transaction::close_escrow_spl_rate!(
-                    ctx.accounts.token_program.to_account_info(),
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.seller_token_account.to_account_info(),
-                    ctx.accounts.commission_auth.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]],
-                    ctx.accounts.commission_transaction.metadata.transaction_price,
-                    ctx.accounts.commission_transaction.close_rate
-                )?;
-                orbit_transaction::close_escrow_spl_rate!(
-                    ctx.accounts.token_program.to_account_info(),
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.buyer_token_account.to_account_info(),
-                    ctx.accounts.commission_auth.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]],
-                    ctx.accounts.commission_transaction.metadata.transaction_price,
-                    100
-                )?;
-                orbit_product::cpi::commission_increment_times_sold(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.product_program.to_account_info(),
-                        orbit_product::cpi::accounts::UpdateCommissionQuantityInternal{
-                            product: ctx.accounts.commission_product.to_account_info(),
-                            caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                            caller: ctx.accounts.commission_program.to_account_info()
-                        },
-                        &[&[b"market_authority", &[*auth_bump]]]
-                    )
-                )?;
-                orbit_transaction::post_tx_incrementing!(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    ctx.accounts.buyer_account.to_account_info(),
-                    ctx.accounts.seller_account.to_account_info(),
-                    ctx.accounts.commission_auth.to_account_info(),
-                    ctx.accounts.commission_program.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-        }else{
-            return err!(CommissionMarketErrors::InvalidAuthBump)
-        }?;
-
-        orbit_transaction::cpi::clear_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
-        Ok(())
-    }
-
-    fn fund_escrow_sol(ctx: Context<FundEscrowSol>) -> Result<()>{
-        invoke(
-            &transfer(
-                &ctx.accounts.buyer_wallet.key(),
-                &ctx.accounts.escrow_account.key(),
-                ctx.accounts.commission_transaction.metadata.transaction_price
-            ),
-            &[
-                ctx.accounts.buyer_wallet.to_account_info(),
-                ctx.accounts.escrow_account.to_account_info()
-            ]
-        ).expect("could not fund escrow");
-        ctx.accounts.commission_transaction.metadata.funded = true;
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
-        Ok(())
-    }
-
-    fn fund_escrow_spl(ctx: Context<FundEscrowSpl>) -> Result<()>{
-        anchor_spl::token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(), 
-                anchor_spl::token::Transfer{
-                    from: ctx.accounts.buyer_token_account.to_account_info(),
-                    to: ctx.accounts.escrow_account.to_account_info(),
-                    authority: ctx.accounts.buyer_wallet.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.transaction_price
-        ).expect("could not fund escrow account. maybe check your balance");
-        ctx.accounts.commission_transaction.metadata.funded = true;
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
-        Ok(())
-    }
-
-    fn close_transaction_account(ctx: Context<CloseTransactionAccount>) -> Result<()>{
-        ctx.accounts.commission_transaction.close(ctx.accounts.buyer_wallet.to_account_info())
-    }
-
-    fn seller_early_decline_sol(ctx: Context<SellerEarlyDeclineSol>) -> Result<()>{
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
-        if ctx.accounts.commission_transaction.metadata.rate == 100{
-            market_accounts::cpi::increment_dispute_discounts(
-                CpiContext::new(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_account.to_account_info(),
-                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                        caller: ctx.accounts.commission_program.to_account_info()
-                    }
-                )
-            )?;
-        };
-
-        let comm_tx = ctx.accounts.commission_transaction.key();
-        let comm_seed = comm_tx.as_ref();
-        let buyer_log = ctx.accounts.buyer_transactions_log.key();
-        let buyer_tx_log_seed = buyer_log.as_ref();
-
-        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
-            orbit_transaction::close_escrow_sol_rate!(
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.seller_wallet.to_account_info(),
-                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                ctx.accounts.commission_transaction.close_rate
-            ).expect("could not transfer tokens");
-            orbit_transaction::close_escrow_sol_rate!(
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.buyer_wallet.to_account_info(),
-                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                100
-            ).expect("could not transfer tokens");
-        }else{
-            return err!(CommissionMarketErrors::InvalidEscrowBump)
-        };
-
-        if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
-            orbit_transaction::close_escrow_sol_rate!(
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.buyer_wallet.to_account_info(),
-                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
-                100
-            )?;
-        }else{
-            return err!(CommissionMarketErrors::InvalidEscrowBump)
-        };
-        
-        orbit_transaction::cpi::clear_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-        Ok(())
-    }
-
-    fn seller_early_decline_spl(ctx: Context<SellerEarlyDeclineSpl>) -> Result<()>{
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
-
-        if ctx.accounts.commission_transaction.metadata.rate == 100{
-            market_accounts::cpi::increment_dispute_discounts(
-                CpiContext::new(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_account.to_account_info(),
-                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                        caller: ctx.accounts.commission_program.to_account_info()
-                    }
-                )
-            )?;
-        }
-
-        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
-            orbit_transaction::close_escrow_spl_rate!(
-                ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.seller_token_account.to_account_info(),
-                ctx.accounts.commission_auth.to_account_info(),
-                &[&[b"market_authority", &[*auth_bump]]],
-                ctx.accounts.commission_transaction.metadata.transaction_price,
-                ctx.accounts.commission_transaction.close_rate
-            ).expect("could not transfer tokens");
-            orbit_transaction::close_escrow_spl_rate!(
-                ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.buyer_token_account.to_account_info(),
-                ctx.accounts.commission_auth.to_account_info(),
-                &[&[b"market_authority", &[*auth_bump]]],
-                ctx.accounts.commission_transaction.metadata.transaction_price,
-                100
-            ).expect("could not transfer tokens");
-        }else{
-            return err!(CommissionMarketErrors::InvalidAuthBump)
-        }; 
-        
-        orbit_transaction::cpi::clear_seller_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_commissions_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            ),
-            ctx.accounts.commission_transaction.metadata.seller_tx_index
-        )?;
-
-        Ok(())
-
-    }
-
-}
-
-//////////////////////////////////////////////////////////////////////////
-/// BUYER CONFIRMATIONS
-
-#[derive(Accounts)]
-pub struct BuyerConfirmation<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
-    )]
-    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions: Box<Account<'info, BuyerOpenTransactions>>,
-
-    pub buyer_wallet: Signer<'info>,
-}
-
-pub fn confirm_delivered_handler(ctx: Context<BuyerConfirmation>) -> Result<()>{
-    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::Shipped{
-        return err!(CommissionMarketErrors::WaitingForSellerData);
-    }
-    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedDelivery;
-
-    Ok(())
-}
-
-pub fn confirm_accept_handler(ctx: Context<BuyerConfirmation>) -> Result<()>{
-    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::BuyerConfirmedDelivery{
-        return err!(CommissionMarketErrors::DidNotConfirmDelivery);
-    }
-    ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Accept;
-    ctx.accounts.commission_transaction.close_rate = ctx.accounts.commission_transaction.metadata.rate;
-    // we dont set state here because we need to wait for the seller to release the final keys
-    Ok(())
-}
-
-#[derive(Accounts)]
-pub struct BuyerDeny<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        mut
-    )]
-    pub buyer_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions: Box<Account<'info, BuyerOpenTransactions>>,
-
-    #[account(
-        address = buyer_account.wallet
-    )]
-    pub buyer_wallet: Signer<'info>,
-    
-    #[account(
-        seeds = [b"market_authority"],
-        bump
-    )]
-    pub commission_auth: SystemAccount<'info>,
-
-    pub commission_program: Program<'info, OrbitCommissionMarket>,
-
-    pub market_accounts_program: Program<'info, OrbitMarketAccounts>
-}
-
-pub fn deny_accept_handler(ctx: Context<BuyerDeny>) -> Result<()>{
-    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::BuyerConfirmedDelivery{
-        return err!(CommissionMarketErrors::DidNotConfirmDelivery);
-    }
-    if ctx.accounts.commission_transaction.metadata.rate == 100{
-        market_accounts::cpi::increment_dispute_discounts(
-            CpiContext::new(
-                ctx.accounts.market_accounts_program.to_account_info(),
-                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                    market_account: ctx.accounts.buyer_account.to_account_info(),
-                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
-                    caller: ctx.accounts.commission_program.to_account_info()
-                }
-            )
-        )?;
-    }
-    ctx.accounts.commission_transaction.metadata.rate = 0;
-    ctx.accounts.commission_transaction.close_rate = 0;
-    ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Declined;
-    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
-
-    Ok(())
-}
-
-///////////////////////////////////////////////////////////////////////
-/// SELLER CONFIRMATIONS
-
-#[derive(Accounts)]
-pub struct SellerAcceptTransaction<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::Opened
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        address = seller_market_account.wallet
-    )]
-    pub wallet: Signer<'info>
-}
-
-pub fn seller_accept_transaction_handler(ctx: Context<SellerAcceptTransaction>) -> Result<()>{
-    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::SellerConfirmed;
-    Ok(())
-}
-
-#[derive(Accounts)]
-pub struct CommitInitData<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        address = seller_market_account.wallet
-    )]
-    pub seller_wallet: Signer<'info>,
-}
-
-pub fn commit_init_keys_handler(ctx: Context<CommitInitData>, submission_keys: Vec<Pubkey>) -> Result<()>{   
-    if submission_keys.len() > 64{
-        return err!(CommissionMarketErrors::IndexOutOfRange)
-    }
-
-    ctx.accounts.commission_transaction.num_keys = submission_keys.len() as u64;
-    ctx.accounts.commission_transaction.key_arr = submission_keys;
-    Ok(())
-}
-
-pub fn commit_link_handler(ctx: Context<CommitInitData>, link: String) -> Result<()>{
-    ctx.accounts.commission_transaction.data_address = link;
-    Ok(())
-}
-
-pub fn update_status_to_shipping_handler(ctx: Context<CommitInitData>) -> Result<()>{
-    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Shipped;
-    Ok(())
-}
-
-#[derive(Accounts)]
-pub struct CommitSubKeys<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        address = seller_market_account.wallet
-    )]
-    pub seller_wallet: Signer<'info>,
-}
-
-pub fn commit_subkeys_handler(ctx: Context<CommitSubKeys>, indexes: Vec<u8>) -> Result<()>{
-    for index in indexes{
-        if index > ctx.accounts.commission_transaction.key_arr.len() as u8{
-            return err!(CommissionMarketErrors::IndexOutOfRange)
-        }
-
-        let acc = &ctx.remaining_accounts[index as usize];
-        if ! acc.is_signer{
-            return err!(CommissionMarketErrors::CorruptPrivateKeyFormat);
-        }
-
-        if Pubkey::find_program_address(&[acc.key().as_ref()], &id()).0 != ctx.accounts.commission_transaction.key_arr[index as usize]{
-            return err!(CommissionMarketErrors::IncorrectPrivateKey);
-        }
-
-        ctx.accounts.commission_transaction.num_keys &= u64::MAX - (1 << index);
-        ctx.accounts.commission_transaction.key_arr[index as usize] = acc.key();
-    }
-
-    if ctx.accounts.commission_transaction.num_keys == 0{
-        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
-    }
-
-    Ok(())
-}
-
-///////////////////////////////////////////////////////////////////////
-/// ACCOUNT HELPERS (leave a review!)
-
-#[derive(Accounts)]
-pub struct LeaveReview<'info>{
-    /////////////////////////////////////////////////
-    /// TX
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::Closed
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    /////////////////////////////////////////////////
-    /// REVIEW RELATED
-    #[account(
-        mut,
-        constraint = 
-        (reviewer.voter_id == commission_transaction.metadata.seller) ||
-        (reviewer.voter_id == commission_transaction.metadata.buyer)
-    )]
-    pub reviewed_account: Box<Account<'info, OrbitMarketAccount>>,
-
-    #[account(
-        constraint = 
-        (reviewer.voter_id == commission_transaction.metadata.seller) ||
-        (reviewer.voter_id == commission_transaction.metadata.buyer),
-        seeds = [
-            b"orbit_account",
-            wallet.key().as_ref()
-        ],
-        bump,
-        seeds::program = market_accounts::ID
-    )]
-    pub reviewer: Box<Account<'info, OrbitMarketAccount>>,
-
-    #[account(
-        address = reviewer.wallet
-    )]
-    pub wallet: Signer<'info>,
-
-    /////////////////////////////////////////////////
-    /// EXTRANEOUS CPI
-    #[account(
-        seeds = [b"market_authority"],
-        bump
-    )]
-    pub commission_auth: SystemAccount<'info>,
-    
-    pub commission_program: Program<'info, OrbitCommissionMarket>,
-
-    pub accounts_program: Program<'info, OrbitMarketAccounts>
-}
-
-impl <'a> OrbitMarketAccountTrait<'a, LeaveReview<'a>> for CommissionTransaction{
-    fn leave_review(ctx: Context<LeaveReview>, rating: u8) -> Result<()>{
-        if ctx.accounts.reviewer.key() == ctx.accounts.reviewed_account.key(){
-            return err!(ReviewErrors::InvalidReviewAuthority)
-        };
-        if rating == 0 || rating > 5{
-            return err!(ReviewErrors::RatingOutsideRange)
-        };
-
-        if ctx.accounts.commission_transaction.metadata.seller == ctx.accounts.reviewer.voter_id && !ctx.accounts.commission_transaction.metadata.reviews.seller{
-            if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
-                orbit_transaction::submit_rating_with_signer!(
-                    ctx.accounts.accounts_program.to_account_info(),
-                    ctx.accounts.reviewed_account.to_account_info(),
-                    ctx.accounts.commission_auth.to_account_info(),
-                    ctx.accounts.commission_program.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]],
-                    rating
-                )?;
-                ctx.accounts.commission_transaction.metadata.reviews.seller = true;
-            }else{
-                return err!(MarketAccountErrors::CannotCallOrbitAccountsProgram)
-            };
-        }else
-        if ctx.accounts.commission_transaction.metadata.buyer == ctx.accounts.reviewer.voter_id && !ctx.accounts.commission_transaction.metadata.reviews.buyer{
-            if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
-                orbit_transaction::submit_rating_with_signer!(
-                    ctx.accounts.accounts_program.to_account_info(),
-                    ctx.accounts.reviewed_account.to_account_info(),
-                    ctx.accounts.commission_auth.to_account_info(),
-                    ctx.accounts.commission_program.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]],
-                    rating
-                )?;
-                ctx.accounts.commission_transaction.metadata.reviews.buyer = true;
-                 
-            }else{
-                return err!(MarketAccountErrors::CannotCallOrbitAccountsProgram)
-            }
-        }else
-        {
-            return err!(ReviewErrors::InvalidReviewAuthority)
-        };
-
-        Ok(())
-    }
-
-}
-
-//////////////////////////////////////////////////////////////////////////
-/// COMMISSION SPECIFIC FIELDS
-
-/// COMMIT PREVIEW FROM SELLER
-
-#[derive(Accounts)]
-pub struct CommitPreview<'info>{
-    #[account(
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        address = seller_market_account.wallet
-    )]
-    pub seller_wallet: Signer<'info>,
-}
-
-pub fn commit_preview_handler(ctx: Context<CommitPreview>, link: String) -> Result<()>{
-    ctx.accounts.commission_transaction.preview_address = link;
-    Ok(())
-}
-
-/// RATE UPDATE UTILS FOR PREVIEW
-
-#[derive(Accounts)]
-pub struct UpdateRate<'info>{
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        has_one = wallet,
-        constraint = 
-        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
-        (proposer_account.voter_id == commission_transaction.metadata.buyer)
-    )]
-    pub proposer_account: Account<'info, OrbitMarketAccount>,
-
-    pub wallet: Signer<'info>,
-}
-
-pub fn propose_rate_handler(ctx: Context<UpdateRate>, new_rate: u8) -> Result<()>{
-    ctx.accounts.commission_transaction.preview_rate = new_rate;
-    ctx.accounts.commission_transaction.last_rate_offerer = ctx.accounts.proposer_account.voter_id;
-    Ok(())
-}
-
-pub fn accept_rate_handler(ctx: Context<UpdateRate>) -> Result<()>{
-    if ctx.accounts.proposer_account.voter_id == ctx.accounts.commission_transaction.last_rate_offerer{
-        return err!(CommissionMarketErrors::InvalidRateAcceptor)
-    };
-    ctx.accounts.commission_transaction.last_rate_offerer = ctx.accounts.commission_transaction.metadata.seller;
-    ctx.accounts.commission_transaction.close_rate = ctx.accounts.commission_transaction.preview_rate;
-    Ok(())
-}
+use std::collections::BTreeMap;
+use anchor_lang::{
+    prelude::*,
+    AccountsClose,
+    solana_program::{
+        program::{
+            invoke,
+            invoke_signed
+        },
+        system_instruction::transfer
+    }
+};
+use orbit_transaction::{
+    transaction_trait::OrbitTransactionTrait,
+    transaction_struct::TransactionState,
+    TransactionReviews,
+    BuyerOpenTransactions,
+    TransactionErrors, SellerOpenTransactions
+};
+use market_accounts::{
+    market_account::OrbitMarketAccount, program::OrbitMarketAccounts,
+    structs::market_account_trait::OrbitMarketAccountTrait,
+    MarketAccountErrors,
+    ReviewErrors
+};
+use anchor_spl::token::TokenAccount;
+use crate::{
+    id,
+    CommissionTransaction,
+
+    CommissionMarketErrors,
+    OpenCommissionTransactionSol,
+    OpenCommissionTransactionSpl,
+    CloseCommissionTransactionSol,
+    CloseCommissionTransactionSpl,
+    FundEscrowSol,
+    FundEscrowSpl,
+    BuyerDecisionState, program::OrbitCommissionMarket, SellerEarlyDeclineSpl, SellerEarlyDeclineSol,
+    MilestoneState,
+    DisputeState,
+    CONFIRMATION_WINDOW,
+    CONTEST_WINDOW,
+    BPS_DENOMINATOR,
+    MAX_RATE_OFFERS,
+    MAX_RATE_ROUNDS,
+    MAX_RATE_NOTE_LEN,
+    RateOffer,
+    RateOfferStatus,
+    ensure_bps_rates,
+    CommissionTransition,
+    emit_transition,
+    token_interface,
+    MAX_KEYS,
+    check_link,
+    fit_transaction,
+    refund_seller_rent,
+    RentPayer,
+    pending_key_mask,
+    DeliverableReleased,
+    SealedKey,
+    MAX_SEALED_KEY_LEN,
+    MAX_PREVIEW_ROUNDS,
+    PreviewRound,
+    consume_rate_round,
+    load_product_terms,
+    check_listing,
+    Referral,
+    referral_payee,
+    emit_referral,
+    close_payout,
+    pay_out_sol,
+    pay_out_spl,
+    delivery_window,
+    review_add_ons,
+    add_to_price,
+    discount_price,
+    CommissionOutcome,
+    REVIEW_WINDOW,
+    ReviewSubmitted
+};
+
+////////////////////////////////////////////////////////////////////
+/// ORBIT BASE TRANSACTION FUNCTIONALITIES
+
+#[derive(Accounts)]
+pub struct CloseTransactionAccount<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::Closed,
+    )]
+    pub commission_transaction: Account<'info, CommissionTransaction>,
+
+    #[account(
+        has_one = wallet,
+        constraint = 
+        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
+        (proposer_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub proposer_account: Account<'info, OrbitMarketAccount>,
+    
+    pub wallet: Signer<'info>,
+
+    #[account(
+        constraint = {buyer_account.voter_id == commission_transaction.metadata.buyer}
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>
+}
+
+////////////////////////////////////////////////////////////////////
+/// OPEN
+/// shared by the trait opens and instant accept so add-ons, discounts and listing checks
+/// work the same however the transaction is opened. the buyer's encryption key comes in here
+/// too, all zero leaves it for register_encryption_key
+
+#[allow(clippy::too_many_arguments)]
+pub fn open_commission_sol<'info>(accounts: &mut OpenCommissionTransactionSol<'info>, bumps: &BTreeMap<String, u8>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: &[u8], buyer_encryption_key: [u8; 32]) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    let terms = load_product_terms(&accounts.product_terms)?;
+    check_listing(&terms, price, &System::id())?;
+    let (mut price, mut add_ons) = add_to_price(&terms, &System::id(), price, add_on_ids)?;
+    if use_discount && accounts.buyer_market_account.dispute_discounts > 0{
+        accounts.commission_transaction.metadata.rate = 100;
+        accounts.commission_transaction.rate_bps = BPS_DENOMINATOR;
+        accounts.commission_transaction.used_discount = true;
+        price = discount_price(price, &mut add_ons, accounts.commission_config.discount_rate)?;
+        
+        market_accounts::cpi::decrement_dispute_discounts(
+            CpiContext::new_with_signer(
+                accounts.market_account_program.to_account_info(),
+                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                    market_account: accounts.buyer_market_account.to_account_info(),
+                    caller_auth: accounts.commission_auth.to_account_info(),
+                    caller: accounts.commission_program.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+    }else{
+        accounts.commission_transaction.metadata.rate = ((BPS_DENOMINATOR - accounts.commission_config.fee_bps) / 100) as u8;
+        accounts.commission_transaction.rate_bps = BPS_DENOMINATOR - accounts.commission_config.fee_bps;
+        accounts.commission_transaction.used_discount = false;
+    }
+    accounts.commission_transaction.rates_in_bps = true;
+    accounts.commission_transaction.metadata.buyer = accounts.buyer_market_account.voter_id;
+    accounts.commission_transaction.metadata.seller = accounts.seller_market_account.voter_id;
+    accounts.commission_transaction.metadata.product = accounts.commission_product.metadata.index;
+    accounts.commission_transaction.metadata.transaction_state = TransactionState::Opened;
+    accounts.commission_transaction.metadata.transaction_price = price;
+    accounts.commission_transaction.add_ons = add_ons;
+    accounts.commission_transaction.buyer_encryption_key = buyer_encryption_key;
+    accounts.commission_transaction.metadata.funded = false;
+    accounts.commission_transaction.metadata.currency = System::id();
+
+    accounts.commission_transaction.num_keys = 0;
+    accounts.commission_transaction.final_decision = BuyerDecisionState::Null;
+    accounts.commission_transaction.free_revisions = terms.map_or(0, |terms| terms.free_revisions);
+
+    accounts.commission_transaction.metadata.reviews = TransactionReviews{
+        buyer: false,
+        seller: false
+    };
+    fit_transaction(
+        &mut accounts.commission_transaction,
+        &accounts.buyer_wallet.to_account_info(),
+        RentPayer::Buyer,
+        &accounts.system_program.to_account_info()
+    )?;
+
+    orbit_transaction::cpi::add_buyer_commissions_transaction(
+        CpiContext::new_with_signer(
+            accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddBuyerCommissionsTransactions{
+                buyer_account: accounts.buyer_market_account.to_account_info(),
+                wallet: accounts.buyer_wallet.to_account_info(),
+                transactions_log: accounts.buyer_transactions_log.to_account_info(),
+                tx: accounts.commission_transaction.to_account_info()
+            },
+            &[&[b"market_authority", &[*auth_bump]]]
+        ),
+        buyer_index
+    )?;
+    orbit_transaction::cpi::add_seller_commissions_transaction(
+        CpiContext::new(
+            accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddSellerCommissionsTransactions{
+                transactions_log: accounts.seller_transactions_log.to_account_info(),
+                tx: accounts.commission_transaction.to_account_info()
+            }
+        ),
+        seller_index
+    )?;
+
+    emit_transition(&accounts.commission_transaction, CommissionTransition::Opened)?;
+    if !accounts.commission_transaction.add_ons.is_empty(){
+        let added = accounts.commission_transaction.add_ons.iter().map(|add_on| add_on.price).sum();
+        emit_transition(&accounts.commission_transaction, CommissionTransition::AddOnsSelected{amount: added})?;
+    }
+    if buyer_encryption_key != [0; 32]{
+        emit_transition(&accounts.commission_transaction, CommissionTransition::EncryptionKeyRegistered)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn open_commission_spl<'info>(accounts: &mut OpenCommissionTransactionSpl<'info>, bumps: &BTreeMap<String, u8>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: &[u8], buyer_encryption_key: [u8; 32]) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    let terms = load_product_terms(&accounts.product_terms)?;
+    check_listing(&terms, price, &accounts.token_mint.key())?;
+    let (mut price, mut add_ons) = add_to_price(&terms, &accounts.token_mint.key(), price, add_on_ids)?;
+    if use_discount && accounts.buyer_market_account.dispute_discounts > 0{
+        accounts.commission_transaction.metadata.rate = 100;
+        accounts.commission_transaction.rate_bps = BPS_DENOMINATOR;
+        accounts.commission_transaction.used_discount = true;
+        price = discount_price(price, &mut add_ons, accounts.commission_config.discount_rate)?;
+        
+        market_accounts::cpi::decrement_dispute_discounts(
+            CpiContext::new_with_signer(
+                accounts.market_account_program.to_account_info(),
+                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                    market_account: accounts.buyer_market_account.to_account_info(),
+                    caller_auth: accounts.commission_auth.to_account_info(),
+                    caller: accounts.commission_program.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+    }else{
+        accounts.commission_transaction.metadata.rate = ((BPS_DENOMINATOR - accounts.commission_config.fee_bps) / 100) as u8;
+        accounts.commission_transaction.rate_bps = BPS_DENOMINATOR - accounts.commission_config.fee_bps;
+        accounts.commission_transaction.used_discount = false;
+    }
+    accounts.commission_transaction.rates_in_bps = true;
+    accounts.commission_transaction.metadata.buyer = accounts.buyer_market_account.voter_id;
+    accounts.commission_transaction.metadata.seller = accounts.seller_market_account.voter_id;
+    accounts.commission_transaction.metadata.product = accounts.commission_product.metadata.index;
+    accounts.commission_transaction.metadata.transaction_state = TransactionState::Opened;
+    accounts.commission_transaction.metadata.transaction_price = price;
+    accounts.commission_transaction.add_ons = add_ons;
+    accounts.commission_transaction.buyer_encryption_key = buyer_encryption_key;
+    accounts.commission_transaction.metadata.funded = false;
+    accounts.commission_transaction.metadata.currency = accounts.token_mint.key();
+
+    let comm_tx = accounts.commission_transaction.key();
+    let buyer_log = accounts.buyer_transactions_log.key();
+    if let Some(escrow_bump) = bumps.get("escrow_account"){
+        token_interface::create_escrow(
+            accounts.token_program.to_account_info(),
+            accounts.escrow_account.to_account_info(),
+            accounts.token_mint.to_account_info(),
+            &accounts.commission_auth.key(),
+            accounts.buyer_wallet.to_account_info(),
+            accounts.system_program.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_tx.as_ref(), buyer_log.as_ref(), &[*escrow_bump]]]
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidEscrowBump)
+    };
+    
+    accounts.commission_transaction.num_keys = 0;
+    accounts.commission_transaction.final_decision = BuyerDecisionState::Null;
+    accounts.commission_transaction.free_revisions = terms.map_or(0, |terms| terms.free_revisions);
+
+    accounts.commission_transaction.metadata.reviews = TransactionReviews{
+        buyer: false,
+        seller: false
+    };
+    fit_transaction(
+        &mut accounts.commission_transaction,
+        &accounts.buyer_wallet.to_account_info(),
+        RentPayer::Buyer,
+        &accounts.system_program.to_account_info()
+    )?;
+
+    orbit_transaction::cpi::add_buyer_commissions_transaction(
+        CpiContext::new_with_signer(
+            accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddBuyerCommissionsTransactions{
+                buyer_account: accounts.buyer_market_account.to_account_info(),
+                wallet: accounts.buyer_wallet.to_account_info(),
+                transactions_log: accounts.buyer_transactions_log.to_account_info(),
+                tx: accounts.commission_transaction.to_account_info()
+            },
+            &[&[b"market_authority", &[*auth_bump]]]
+        ),
+        buyer_index
+    )?;
+    orbit_transaction::cpi::add_seller_commissions_transaction(
+        CpiContext::new(
+            accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddSellerCommissionsTransactions{
+                transactions_log: accounts.seller_transactions_log.to_account_info(),
+                tx: accounts.commission_transaction.to_account_info()
+            }
+        ),
+        seller_index
+    )?;
+    emit_transition(&accounts.commission_transaction, CommissionTransition::Opened)?;
+    if !accounts.commission_transaction.add_ons.is_empty(){
+        let added = accounts.commission_transaction.add_ons.iter().map(|add_on| add_on.price).sum();
+        emit_transition(&accounts.commission_transaction, CommissionTransition::AddOnsSelected{amount: added})?;
+    }
+    if buyer_encryption_key != [0; 32]{
+        emit_transition(&accounts.commission_transaction, CommissionTransition::EncryptionKeyRegistered)?;
+    }
+    Ok(())
+}
+
+impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, OpenCommissionTransactionSol<'a>, OpenCommissionTransactionSpl<'b>, CloseCommissionTransactionSol<'c>, CloseCommissionTransactionSpl<'d>, FundEscrowSol<'e>, FundEscrowSpl<'f>, CloseTransactionAccount<'g>, SellerEarlyDeclineSol<'h>, SellerEarlyDeclineSpl<'i>> for CommissionTransaction{
+    fn open_sol(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
+        open_commission_sol(ctx.accounts, &ctx.bumps, seller_index, buyer_index, price, use_discount, &[], [0; 32])
+    }
+
+    fn open_spl(ctx: Context<OpenCommissionTransactionSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
+        open_commission_spl(ctx.accounts, &ctx.bumps, seller_index, buyer_index, price, use_discount, &[], [0; 32])
+    }
+
+    fn close_sol(ctx: Context<'_, '_, '_, 'c, CloseCommissionTransactionSol<'c>>) -> Result<()>{
+        let comm_tx = ctx.accounts.commission_transaction.key();
+        let comm_seed = comm_tx.as_ref();
+        let buyer_log = ctx.accounts.buyer_transactions_log.key();
+        let buyer_tx_log_seed = buyer_log.as_ref();
+
+        ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+        let referral = referral_payee(ctx.remaining_accounts, &ctx.accounts.buyer_account, None)?;
+        let payout = close_payout(
+            &ctx.accounts.commission_transaction,
+            ctx.accounts.commission_config.referral_bps,
+            matches!(referral, Referral::Payee(_))
+        );
+
+        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+            pay_out_sol(
+                &ctx.accounts.escrow_account.to_account_info(),
+                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                &payout,
+                &referral,
+                &ctx.accounts.seller_wallet.to_account_info(),
+                &ctx.accounts.treasury_wallet.to_account_info(),
+                &ctx.accounts.buyer_wallet.to_account_info()
+            )?;
+            // anything the buyer put in past the price
+            orbit_transaction::close_escrow_sol_rate!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                100
+            ).expect("could not transfer tokens");
+        }else{
+            return err!(CommissionMarketErrors::InvalidEscrowBump)
+        };
+        emit_referral(&ctx.accounts.commission_transaction, &ctx.accounts.buyer_account, &referral, ctx.accounts.treasury_wallet.key(), payout.referral);
+
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            orbit_transaction::post_tx_incrementing!(
+                ctx.accounts.market_account_program.to_account_info(),
+                ctx.accounts.buyer_account.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                ctx.accounts.commission_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]]
+            )?;
+            orbit_product::cpi::commission_increment_times_sold(
+                CpiContext::new_with_signer(
+                    ctx.accounts.product_program.to_account_info(),
+                    orbit_product::cpi::accounts::UpdateCommissionQuantityInternal{
+                        product: ctx.accounts.commission_product.to_account_info(),
+                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                        caller: ctx.accounts.commission_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        };
+
+        orbit_transaction::cpi::clear_seller_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
+                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        orbit_transaction::cpi::clear_buyer_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
+                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+        let outcome = close_outcome(&ctx.accounts.commission_transaction);
+        record_outcome(&mut ctx.accounts.commission_transaction, outcome)?;
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Closed)?;
+        Ok(())
+    }
+
+    fn close_spl(ctx: Context<'_, '_, '_, 'd, CloseCommissionTransactionSpl<'d>>) -> Result<()>{
+        ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+        let referral = referral_payee(ctx.remaining_accounts, &ctx.accounts.buyer_account, Some(&ctx.accounts.commission_transaction.metadata.currency))?;
+        let payout = close_payout(
+            &ctx.accounts.commission_transaction,
+            ctx.accounts.commission_config.referral_bps,
+            matches!(referral, Referral::Payee(_))
+        );
+
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+                pay_out_spl(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.escrow_account.to_account_info(),
+                    &ctx.accounts.token_mint.to_account_info(),
+                    &ctx.accounts.commission_auth.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]],
+                    &payout,
+                    &referral,
+                    &ctx.accounts.seller_token_account.to_account_info(),
+                    &ctx.accounts.treasury_ata.to_account_info(),
+                    &ctx.accounts.buyer_token_account.to_account_info()
+                )?;
+                // whatever the funding reserve did not spend on transfer fees
+                let buyer_amt = token_interface::amount(&ctx.accounts.escrow_account.to_account_info())?;
+                token_interface::transfer_checked(
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.escrow_account.to_account_info(),
+                    ctx.accounts.token_mint.to_account_info(),
+                    ctx.accounts.buyer_token_account.to_account_info(),
+                    ctx.accounts.commission_auth.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]],
+                    buyer_amt
+                )?;
+                // the buyer paid the escrow's rent at open. token-2022 won't close it while
+                // it still holds withheld transfer fees, those escrows stay until harvested
+                if token_interface::withheld_fees(&ctx.accounts.escrow_account.to_account_info())? == 0{
+                    token_interface::close_escrow(
+                        ctx.accounts.token_program.to_account_info(),
+                        ctx.accounts.escrow_account.to_account_info(),
+                        ctx.accounts.buyer_wallet.to_account_info(),
+                        ctx.accounts.commission_auth.to_account_info(),
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )?;
+                }
+                emit_referral(&ctx.accounts.commission_transaction, &ctx.accounts.buyer_account, &referral, ctx.accounts.treasury_ata.key(), payout.referral);
+                orbit_product::cpi::commission_increment_times_sold(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.product_program.to_account_info(),
+                        orbit_product::cpi::accounts::UpdateCommissionQuantityInternal{
+                            product: ctx.accounts.commission_product.to_account_info(),
+                            caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                            caller: ctx.accounts.commission_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+                orbit_transaction::post_tx_incrementing!(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    ctx.accounts.buyer_account.to_account_info(),
+                    ctx.accounts.seller_account.to_account_info(),
+                    ctx.accounts.commission_auth.to_account_info(),
+                    ctx.accounts.commission_program.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        }?;
+
+        orbit_transaction::cpi::clear_seller_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
+                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        orbit_transaction::cpi::clear_buyer_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
+                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+        let outcome = close_outcome(&ctx.accounts.commission_transaction);
+        record_outcome(&mut ctx.accounts.commission_transaction, outcome)?;
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Closed)?;
+        Ok(())
+    }
+
+    fn fund_escrow_sol(ctx: Context<FundEscrowSol>) -> Result<()>{
+        invoke(
+            &transfer(
+                &ctx.accounts.buyer_wallet.key(),
+                &ctx.accounts.escrow_account.key(),
+                ctx.accounts.commission_transaction.metadata.transaction_price
+            ),
+            &[
+                ctx.accounts.buyer_wallet.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info()
+            ]
+        ).expect("could not fund escrow");
+        ctx.accounts.commission_transaction.metadata.funded = true;
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
+        ctx.accounts.commission_transaction.delivery_deadline = Clock::get()?.unix_timestamp + delivery_window(&ctx.accounts.commission_transaction);
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Funded)?;
+        Ok(())
+    }
+
+    fn fund_escrow_spl(ctx: Context<FundEscrowSpl>) -> Result<()>{
+        token_interface::fund_escrow(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.commission_transaction.metadata.transaction_price
+        ).expect("could not fund escrow account. maybe check your balance");
+        ctx.accounts.commission_transaction.metadata.funded = true;
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
+        ctx.accounts.commission_transaction.delivery_deadline = Clock::get()?.unix_timestamp + delivery_window(&ctx.accounts.commission_transaction);
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Funded)?;
+        Ok(())
+    }
+
+    /// rent goes back to whoever paid it, see fit_transaction
+    fn close_transaction_account(ctx: Context<CloseTransactionAccount>) -> Result<()>{
+        refund_seller_rent(&ctx.accounts.commission_transaction, &ctx.accounts.seller_wallet.to_account_info())?;
+        ctx.accounts.commission_transaction.close(ctx.accounts.buyer_wallet.to_account_info())
+    }
+
+    fn seller_early_decline_sol(ctx: Context<SellerEarlyDeclineSol>) -> Result<()>{
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+        record_outcome(&mut ctx.accounts.commission_transaction, CommissionOutcome::EarlyDeclined)?;
+        if ctx.accounts.commission_transaction.used_discount{
+            if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_account_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_account.to_account_info(),
+                            caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                            caller: ctx.accounts.commission_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            }else{
+                return err!(CommissionMarketErrors::InvalidAuthBump)
+            };
+            ctx.accounts.commission_transaction.used_discount = false;
+        }
+
+        let comm_tx = ctx.accounts.commission_transaction.key();
+        let comm_seed = comm_tx.as_ref();
+        let buyer_log = ctx.accounts.buyer_transactions_log.key();
+        let buyer_tx_log_seed = buyer_log.as_ref();
+
+        ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+        // no buyer decision, so no fee: the seller keeps their close rate, the rest is refunded
+        let payout = close_payout(&ctx.accounts.commission_transaction, 0, false);
+
+        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+            orbit_transaction::close_escrow_sol_flat!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.seller_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                payout.seller
+            ).expect("could not transfer tokens");
+            // payout.buyer plus the escrow rent
+            orbit_transaction::close_escrow_sol_rate!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                100
+            ).expect("could not transfer tokens");
+        }else{
+            return err!(CommissionMarketErrors::InvalidEscrowBump)
+        };
+        
+        orbit_transaction::cpi::clear_seller_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
+                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        orbit_transaction::cpi::clear_buyer_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
+                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::EarlyDeclined)?;
+        Ok(())
+    }
+
+    fn seller_early_decline_spl(ctx: Context<SellerEarlyDeclineSpl>) -> Result<()>{
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Closed;
+        record_outcome(&mut ctx.accounts.commission_transaction, CommissionOutcome::EarlyDeclined)?;
+
+        if ctx.accounts.commission_transaction.used_discount{
+            if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_account_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_account.to_account_info(),
+                            caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                            caller: ctx.accounts.commission_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            }else{
+                return err!(CommissionMarketErrors::InvalidAuthBump)
+            };
+            ctx.accounts.commission_transaction.used_discount = false;
+        }
+
+        ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+        let payout = close_payout(&ctx.accounts.commission_transaction, 0, false);
+
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            token_interface::transfer_checked(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.seller_token_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                token_interface::gross_up(&ctx.accounts.token_mint.to_account_info(), payout.seller)?
+            ).expect("could not transfer tokens");
+            // payout.buyer plus whatever the funding reserve did not spend on transfer fees
+            let buyer_amt = token_interface::amount(&ctx.accounts.escrow_account.to_account_info())?;
+            token_interface::transfer_checked(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.buyer_token_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                buyer_amt
+            ).expect("could not transfer tokens");
+            if token_interface::withheld_fees(&ctx.accounts.escrow_account.to_account_info())? == 0{
+                token_interface::close_escrow(
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.escrow_account.to_account_info(),
+                    ctx.accounts.buyer_wallet.to_account_info(),
+                    ctx.accounts.commission_auth.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )?;
+            }
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        }; 
+        
+        orbit_transaction::cpi::clear_seller_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearSellerCommissionsTransactions{
+                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        orbit_transaction::cpi::clear_buyer_commissions_transaction(
+            CpiContext::new(
+                ctx.accounts.transaction_program.to_account_info(),
+                orbit_transaction::cpi::accounts::ClearBuyerCommissionsTransactions{
+                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                    caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                    caller: ctx.accounts.commission_program.to_account_info()
+                }
+            ),
+            ctx.accounts.commission_transaction.metadata.seller_tx_index
+        )?;
+
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::EarlyDeclined)?;
+        Ok(())
+
+    }
+
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// BUYER CONFIRMATIONS
+
+#[derive(Accounts)]
+pub struct BuyerConfirmation<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions: Box<Account<'info, BuyerOpenTransactions>>,
+
+    pub buyer_wallet: Signer<'info>,
+}
+
+pub fn confirm_delivered_handler(ctx: Context<BuyerConfirmation>) -> Result<()>{
+    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::Shipped{
+        return err!(CommissionMarketErrors::WaitingForSellerData);
+    }
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedDelivery;
+
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Delivered)?;
+    Ok(())
+}
+
+pub fn confirm_accept_handler(ctx: Context<BuyerConfirmation>) -> Result<()>{
+    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::BuyerConfirmedDelivery{
+        return err!(CommissionMarketErrors::DidNotConfirmDelivery);
+    }
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+    ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Accept;
+    ctx.accounts.commission_transaction.close_rate = ctx.accounts.commission_transaction.metadata.rate;
+    ctx.accounts.commission_transaction.close_rate_bps = ctx.accounts.commission_transaction.rate_bps;
+    // we dont set state here because we need to wait for the seller to release the final keys
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Accepted)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyerDeny<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub market_accounts_program: Program<'info, OrbitMarketAccounts>
+}
+
+pub fn deny_accept_handler(ctx: Context<BuyerDeny>) -> Result<()>{
+    if ctx.accounts.commission_transaction.metadata.transaction_state != TransactionState::BuyerConfirmedDelivery{
+        return err!(CommissionMarketErrors::DidNotConfirmDelivery);
+    }
+    // the deny only sticks once the seller had a chance to contest it, see uphold_deny
+    ctx.accounts.commission_transaction.dispute_state = DisputeState::Denied;
+    ctx.accounts.commission_transaction.contest_deadline = Clock::get()?.unix_timestamp + CONTEST_WINDOW;
+
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Denied)?;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////////////////
+/// SELLER CONFIRMATIONS
+
+#[derive(Accounts)]
+pub struct SellerAcceptTransaction<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::Opened
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub wallet: Signer<'info>
+}
+
+pub fn seller_accept_transaction_handler(ctx: Context<SellerAcceptTransaction>, rejected_add_ons: Vec<u8>) -> Result<()>{
+    review_add_ons(&mut ctx.accounts.commission_transaction, &rejected_add_ons)?;
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::SellerConfirmed;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::SellerAccepted)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CommitInitData<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_init_keys_handler(ctx: Context<CommitInitData>, submission_keys: Vec<Pubkey>) -> Result<()>{   
+    if ctx.accounts.commission_transaction.keys_committed{
+        return err!(CommissionMarketErrors::KeysAlreadyCommitted)
+    }
+    if submission_keys.len() > MAX_KEYS{
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+
+    ctx.accounts.commission_transaction.num_keys = pending_key_mask(submission_keys.len());
+    ctx.accounts.commission_transaction.key_arr = submission_keys;
+    ctx.accounts.commission_transaction.keys_committed = true;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::KeysCommitted{pending_keys: ctx.accounts.commission_transaction.num_keys})?;
+    Ok(())
+}
+
+pub fn commit_link_handler(ctx: Context<CommitInitData>, link: String) -> Result<()>{
+    check_link(&link)?;
+    ctx.accounts.commission_transaction.data_address = link;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::LinkCommitted)?;
+    Ok(())
+}
+
+pub fn update_status_to_shipping_handler(ctx: Context<CommitInitData>) -> Result<()>{
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::Shipped;
+    ctx.accounts.commission_transaction.confirmation_deadline = Clock::get()?.unix_timestamp + CONFIRMATION_WINDOW;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Shipped)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CommitSubKeys<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded)
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// the signer for each index still proves the seller holds the committed key.
+/// what the buyer needs to decrypt comes in `ciphertexts`, sealed to buyer_encryption_key
+pub fn commit_subkeys_handler(ctx: Context<CommitSubKeys>, indexes: Vec<u8>, ciphertexts: Vec<Vec<u8>>) -> Result<()>{
+    if ciphertexts.len() != indexes.len(){
+        return err!(CommissionMarketErrors::InvalidSealedKey)
+    }
+    if !indexes.is_empty() && ctx.accounts.commission_transaction.buyer_encryption_key == [0; 32]{
+        return err!(CommissionMarketErrors::NoEncryptionKey)
+    }
+
+    // before delivery only keys belonging to approved milestones can be released. once every
+    // milestone is approved the rest go too, masks don't have to cover every key
+    let mut releasable = u64::MAX;
+    if ctx.accounts.commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+        && ctx.accounts.commission_transaction.final_decision != BuyerDecisionState::Accept{
+        releasable = ctx.accounts.commission_transaction.milestones.iter()
+            .filter(|m| m.state == MilestoneState::Approved)
+            .fold(0, |mask, m| mask | m.key_mask);
+    }
+
+    for (index, ciphertext) in indexes.into_iter().zip(ciphertexts.into_iter()){
+        if index as usize >= ctx.accounts.commission_transaction.key_arr.len(){
+            return err!(CommissionMarketErrors::IndexOutOfRange)
+        }
+        if ctx.accounts.commission_transaction.num_keys & (1 << index) == 0{
+            return err!(CommissionMarketErrors::KeyAlreadyReleased)
+        }
+        if ciphertext.is_empty() || ciphertext.len() > MAX_SEALED_KEY_LEN{
+            return err!(CommissionMarketErrors::InvalidSealedKey)
+        }
+        if releasable & (1 << index) == 0{
+            return err!(CommissionMarketErrors::InvalidMilestoneState)
+        }
+
+        let acc = &ctx.remaining_accounts[index as usize];
+        if ! acc.is_signer{
+            return err!(CommissionMarketErrors::CorruptPrivateKeyFormat);
+        }
+
+        if Pubkey::find_program_address(&[acc.key().as_ref()], &id()).0 != ctx.accounts.commission_transaction.key_arr[index as usize]{
+            return err!(CommissionMarketErrors::IncorrectPrivateKey);
+        }
+
+        ctx.accounts.commission_transaction.num_keys &= u64::MAX - (1 << index);
+        emit!(DeliverableReleased{
+            transaction: ctx.accounts.commission_transaction.key(),
+            index,
+            ciphertext: ciphertext.clone(),
+            content_hash: ctx.accounts.commission_transaction.content_hashes.get(index as usize).copied().unwrap_or_default()
+        });
+        ctx.accounts.commission_transaction.sealed_keys.push(SealedKey{
+            index,
+            ciphertext
+        });
+    }
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+
+    if ctx.accounts.commission_transaction.num_keys == 0{
+        ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+    }
+
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::SubkeysReleased{pending_keys: ctx.accounts.commission_transaction.num_keys})?;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////////////////
+/// ACCOUNT HELPERS (leave a review!)
+
+/// called by every path that closes a transaction without closing the account
+pub fn record_outcome(tx: &mut CommissionTransaction, outcome: CommissionOutcome) -> Result<()>{
+    tx.outcome = outcome;
+    tx.review_deadline = Clock::get()?.unix_timestamp + REVIEW_WINDOW;
+    Ok(())
+}
+
+/// what a regular close settled on
+pub fn close_outcome(tx: &CommissionTransaction) -> CommissionOutcome{
+    if tx.dispute_state == DisputeState::Ruled{
+        CommissionOutcome::Ruled
+    }else if tx.final_decision == BuyerDecisionState::Accept{
+        CommissionOutcome::Accepted
+    }else{
+        CommissionOutcome::Denied
+    }
+}
+
+/// reviews are for work that actually happened: accepted, or settled by a dispute ruling.
+/// transactions closed before outcomes were recorded fall back to the buyer's decision
+pub fn check_review_eligibility(tx: &CommissionTransaction) -> Result<()>{
+    let eligible = match tx.outcome{
+        CommissionOutcome::Accepted | CommissionOutcome::Ruled => true,
+        CommissionOutcome::Pending => tx.final_decision == BuyerDecisionState::Accept,
+        _ => false
+    };
+    if !eligible{
+        return err!(CommissionMarketErrors::ReviewNotEligible)
+    }
+    if (tx.review_deadline != 0) && (Clock::get()?.unix_timestamp > tx.review_deadline){
+        return err!(CommissionMarketErrors::ReviewWindowClosed)
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeaveReview<'info>{
+    /////////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::Closed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /////////////////////////////////////////////////
+    /// REVIEW RELATED
+    #[account(
+        mut,
+        constraint = 
+        (reviewed_account.voter_id == commission_transaction.metadata.seller) ||
+        (reviewed_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub reviewed_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        constraint = 
+        (reviewer.voter_id == commission_transaction.metadata.seller) ||
+        (reviewer.voter_id == commission_transaction.metadata.buyer),
+        seeds = [
+            b"orbit_account",
+            wallet.key().as_ref()
+        ],
+        bump,
+        seeds::program = market_accounts::ID
+    )]
+    pub reviewer: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = reviewer.wallet
+    )]
+    pub wallet: Signer<'info>,
+
+    /////////////////////////////////////////////////
+    /// EXTRANEOUS CPI
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+    
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub accounts_program: Program<'info, OrbitMarketAccounts>
+}
+
+impl <'a> OrbitMarketAccountTrait<'a, LeaveReview<'a>> for CommissionTransaction{
+    fn leave_review(ctx: Context<LeaveReview>, rating: u8) -> Result<()>{
+        leave_text_review_handler(ctx, rating, [0; 32])
+    }
+
+}
+
+/// leave_review with the hash of a written review stored next to the rating
+pub fn leave_text_review_handler(ctx: Context<LeaveReview>, rating: u8, review_hash: [u8; 32]) -> Result<()>{
+    if ctx.accounts.reviewer.key() == ctx.accounts.reviewed_account.key(){
+        return err!(ReviewErrors::InvalidReviewAuthority)
+    };
+    if rating == 0 || rating > 5{
+        return err!(ReviewErrors::RatingOutsideRange)
+    };
+    check_review_eligibility(&ctx.accounts.commission_transaction)?;
+
+    if ctx.accounts.commission_transaction.metadata.seller == ctx.accounts.reviewer.voter_id && !ctx.accounts.commission_transaction.metadata.reviews.seller{
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            orbit_transaction::submit_rating_with_signer!(
+                ctx.accounts.accounts_program.to_account_info(),
+                ctx.accounts.reviewed_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                ctx.accounts.commission_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                rating
+            )?;
+            ctx.accounts.commission_transaction.metadata.reviews.seller = true;
+            ctx.accounts.commission_transaction.seller_review_hash = review_hash;
+        }else{
+            return err!(MarketAccountErrors::CannotCallOrbitAccountsProgram)
+        };
+    }else
+    if ctx.accounts.commission_transaction.metadata.buyer == ctx.accounts.reviewer.voter_id && !ctx.accounts.commission_transaction.metadata.reviews.buyer{
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            orbit_transaction::submit_rating_with_signer!(
+                ctx.accounts.accounts_program.to_account_info(),
+                ctx.accounts.reviewed_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                ctx.accounts.commission_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                rating
+            )?;
+            ctx.accounts.commission_transaction.metadata.reviews.buyer = true;
+            ctx.accounts.commission_transaction.buyer_review_hash = review_hash;
+        }else{
+            return err!(MarketAccountErrors::CannotCallOrbitAccountsProgram)
+        }
+    }else
+    {
+        return err!(ReviewErrors::InvalidReviewAuthority)
+    };
+
+    emit!(ReviewSubmitted{
+        transaction: ctx.accounts.commission_transaction.key(),
+        reviewer: ctx.accounts.reviewer.voter_id,
+        reviewed: ctx.accounts.reviewed_account.voter_id,
+        rating,
+        review_hash
+    });
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Reviewed{rating})?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// COMMISSION SPECIFIC FIELDS
+
+/// COMMIT PREVIEW FROM SELLER
+
+#[derive(Accounts)]
+pub struct CommitPreview<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// posts the next numbered preview round. once the buyer's free revisions are used up
+/// every further round has to use up a seller rate offer the buyer accepted
+pub fn commit_preview_handler(ctx: Context<CommitPreview>, link: String, content_hash: [u8; 32], rate_round: u8) -> Result<()>{
+    check_link(&link)?;
+    consume_rate_round(&mut ctx.accounts.commission_transaction, rate_round)?;
+
+    let tx = &mut ctx.accounts.commission_transaction;
+    let posted = tx.preview_rounds.len();
+    if posted >= MAX_PREVIEW_ROUNDS{
+        return err!(CommissionMarketErrors::PreviewRoundsExhausted)
+    }
+    // the first round is the preview itself, every one after it is a revision
+    if (posted > tx.free_revisions as usize) && (rate_round == 0){
+        return err!(CommissionMarketErrors::PaidRevisionNeedsRate)
+    }
+    tx.preview_rounds.push(PreviewRound{
+        version: posted as u8 + 1,
+        link: link.clone(),
+        content_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+        rate_round,
+        revision_request: String::new()
+    });
+    tx.preview_address = link;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::PreviewCommitted{version: posted as u8 + 1, rate_round})?;
+    Ok(())
+}
+
+/// RATE UPDATE UTILS FOR PREVIEW
+
+#[derive(Accounts)]
+pub struct UpdateRate<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        has_one = wallet,
+        constraint = 
+        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
+        (proposer_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub proposer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// index of the newest offer if it is still waiting on an answer
+fn pending_rate_offer(tx: &CommissionTransaction) -> Option<usize>{
+    if tx.rate_rounds == 0{
+        return None
+    }
+    let idx = (tx.rate_rounds as usize - 1) % MAX_RATE_OFFERS;
+    match tx.rate_offers.get(idx){
+        Some(offer) if offer.status == RateOfferStatus::Pending => Some(idx),
+        _ => None
+    }
+}
+
+pub fn propose_rate_handler(ctx: Context<UpdateRate>, new_rate_bps: u16, note: String) -> Result<()>{
+    if new_rate_bps > BPS_DENOMINATOR{
+        return err!(CommissionMarketErrors::InvalidRate)
+    }
+    if note.len() > MAX_RATE_NOTE_LEN{
+        return err!(CommissionMarketErrors::RateNoteTooLong)
+    }
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+
+    let tx = &mut ctx.accounts.commission_transaction;
+    if tx.rate_rounds >= MAX_RATE_ROUNDS{
+        return err!(CommissionMarketErrors::RateRoundsExhausted)
+    }
+    if let Some(idx) = pending_rate_offer(tx){
+        tx.rate_offers[idx].status = RateOfferStatus::Countered;
+    }
+    let offer = RateOffer{
+        rate_bps: new_rate_bps,
+        proposer: ctx.accounts.proposer_account.voter_id,
+        timestamp: Clock::get()?.unix_timestamp,
+        note,
+        status: RateOfferStatus::Pending
+    };
+    let slot = tx.rate_rounds as usize % MAX_RATE_OFFERS;
+    if slot < tx.rate_offers.len(){
+        tx.rate_offers[slot] = offer;
+    }else{
+        tx.rate_offers.push(offer);
+    }
+    tx.rate_rounds += 1;
+
+    tx.preview_rate_bps = new_rate_bps;
+    tx.last_rate_offerer = ctx.accounts.proposer_account.voter_id;
+    let rent_payer = RentPayer::of(&ctx.accounts.commission_transaction, ctx.accounts.proposer_account.voter_id);
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.wallet.to_account_info(),
+        rent_payer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RateProposed{round: ctx.accounts.commission_transaction.rate_rounds, rate_bps: new_rate_bps})?;
+    Ok(())
+}
+
+pub fn accept_rate_handler(ctx: Context<UpdateRate>) -> Result<()>{
+    if ctx.accounts.proposer_account.voter_id == ctx.accounts.commission_transaction.last_rate_offerer{
+        return err!(CommissionMarketErrors::InvalidRateAcceptor)
+    };
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+    let idx = match pending_rate_offer(&ctx.accounts.commission_transaction){
+        Some(idx) => idx,
+        None => return err!(CommissionMarketErrors::NoPendingRateOffer)
+    };
+    if ctx.accounts.commission_transaction.preview_rate_bps > BPS_DENOMINATOR{
+        return err!(CommissionMarketErrors::InvalidRate)
+    };
+    ctx.accounts.commission_transaction.rate_offers[idx].status = RateOfferStatus::Accepted;
+    ctx.accounts.commission_transaction.last_rate_offerer = ctx.accounts.commission_transaction.metadata.seller;
+    ctx.accounts.commission_transaction.close_rate_bps = ctx.accounts.commission_transaction.preview_rate_bps;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RateAccepted{round: ctx.accounts.commission_transaction.rate_rounds})?;
+    Ok(())
+}
+
+pub fn reject_rate_handler(ctx: Context<UpdateRate>) -> Result<()>{
+    if ctx.accounts.proposer_account.voter_id == ctx.accounts.commission_transaction.last_rate_offerer{
+        return err!(CommissionMarketErrors::InvalidRateAcceptor)
+    };
+    let idx = match pending_rate_offer(&ctx.accounts.commission_transaction){
+        Some(idx) => idx,
+        None => return err!(CommissionMarketErrors::NoPendingRateOffer)
+    };
+    let round = ctx.accounts.commission_transaction.rate_rounds;
+    if round >= MAX_RATE_ROUNDS{
+        // nothing can counter the last round, turning it down ends the negotiation
+        ctx.accounts.commission_transaction.rate_offers[idx].status = RateOfferStatus::Expired;
+        emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RateExpired{round})?;
+        return Ok(())
+    }
+    ctx.accounts.commission_transaction.rate_offers[idx].status = RateOfferStatus::Rejected;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RateRejected{round})?;
+    Ok(())
+}
+
+pub fn withdraw_rate_handler(ctx: Context<UpdateRate>) -> Result<()>{
+    if ctx.accounts.proposer_account.voter_id != ctx.accounts.commission_transaction.last_rate_offerer{
+        return err!(CommissionMarketErrors::InvalidRateAcceptor)
+    };
+    let idx = match pending_rate_offer(&ctx.accounts.commission_transaction){
+        Some(idx) => idx,
+        None => return err!(CommissionMarketErrors::NoPendingRateOffer)
+    };
+    ctx.accounts.commission_transaction.rate_offers[idx].status = RateOfferStatus::Withdrawn;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RateWithdrawn{round: ctx.accounts.commission_transaction.rate_rounds})?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use market_accounts::{
+    OrbitMarketAccount,
+    program::OrbitMarketAccounts
+};
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    BuyerDecisionState,
+    DisputeState,
+    program::OrbitCommissionMarket,
+    ensure_bps_rates,
+    CommissionTransition,
+    emit_transition
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// DEADLINE CRANKS
+/// permissionless. these only move the transaction into a closable state,
+/// the escrow itself is paid out by close_transaction_sol / close_transaction_spl
+
+/// SELLER NEVER SHIPPED
+
+#[derive(Accounts)]
+pub struct ExpireDelivery<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>
+}
+
+pub fn expire_delivery_handler(ctx: Context<ExpireDelivery>) -> Result<()>{
+    let deadline = ctx.accounts.commission_transaction.delivery_deadline;
+    if deadline == 0 || Clock::get()?.unix_timestamp <= deadline{
+        return err!(CommissionMarketErrors::DeadlineNotReached)
+    }
+
+    if ctx.accounts.commission_transaction.used_discount{
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                        caller: ctx.accounts.commission_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        };
+        ctx.accounts.commission_transaction.used_discount = false;
+    }
+
+    // same outcome as an upheld deny: full refund on close
+    decline(&mut ctx.accounts.commission_transaction);
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::DeliveryExpired)?;
+    Ok(())
+}
+
+fn decline(tx: &mut CommissionTransaction){
+    ensure_bps_rates(tx);
+    tx.metadata.rate = 0;
+    tx.close_rate = 0;
+    tx.rate_bps = 0;
+    tx.close_rate_bps = 0;
+    tx.final_decision = BuyerDecisionState::Declined;
+    tx.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+}
+
+/// SELLER NEVER CONTESTED A DENY
+
+#[derive(Accounts)]
+pub struct UpholdDeny<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.dispute_state == DisputeState::Denied,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>
+}
+
+pub fn uphold_deny_handler(ctx: Context<UpholdDeny>) -> Result<()>{
+    if Clock::get()?.unix_timestamp <= ctx.accounts.commission_transaction.contest_deadline{
+        return err!(CommissionMarketErrors::DeadlineNotReached)
+    }
+
+    if ctx.accounts.commission_transaction.used_discount{
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.commission_auth.to_account_info(),
+                        caller: ctx.accounts.commission_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        };
+        ctx.accounts.commission_transaction.used_discount = false;
+    }
+
+    decline(&mut ctx.accounts.commission_transaction);
+    ctx.accounts.commission_transaction.dispute_state = DisputeState::None;
+    ctx.accounts.commission_transaction.contest_deadline = 0;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::DenyUpheld)?;
+    Ok(())
+}
+
+/// BUYER NEVER DECIDED
+
+#[derive(Accounts)]
+pub struct ExpireConfirmation<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+}
+
+pub fn expire_confirmation_handler(ctx: Context<ExpireConfirmation>) -> Result<()>{
+    let deadline = ctx.accounts.commission_transaction.confirmation_deadline;
+    if deadline == 0 || Clock::get()?.unix_timestamp <= deadline{
+        return err!(CommissionMarketErrors::DeadlineNotReached)
+    }
+
+    // same outcome as a buyer accept, except the seller is not waited on for subkeys
+    ctx.accounts.commission_transaction.final_decision = BuyerDecisionState::Accept;
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+    ctx.accounts.commission_transaction.close_rate = ctx.accounts.commission_transaction.metadata.rate;
+    ctx.accounts.commission_transaction.close_rate_bps = ctx.accounts.commission_transaction.rate_bps;
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::ConfirmationExpired)?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    BuyerDecisionState,
+    DisputeState,
+    MilestoneState,
+    DisputeEvidence,
+    MAX_EVIDENCE,
+    BPS_DENOMINATOR,
+    ensure_bps_rates,
+    CommissionTransition,
+    emit_transition,
+    check_link,
+    fit_transaction,
+    RentPayer
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// DISPUTE PARTICIPANTS
+
+#[derive(Accounts)]
+pub struct DisputeParticipant<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        has_one = wallet,
+        constraint =
+        (participant_account.voter_id == commission_transaction.metadata.seller) ||
+        (participant_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub participant_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// milestone plans can be disputed before delivery once the buyer declines a milestone
+pub fn open_dispute_handler(ctx: Context<DisputeParticipant>) -> Result<()>{
+    let tx = &ctx.accounts.commission_transaction;
+    let milestone_declined = tx.metadata.transaction_state == TransactionState::BuyerFunded
+        && tx.milestones.iter().any(|m| m.state == MilestoneState::Declined);
+    let delivered = tx.metadata.transaction_state == TransactionState::Shipped
+        || tx.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery;
+    if !delivered && !milestone_declined{
+        return err!(CommissionMarketErrors::WaitingForSellerData)
+    }
+    if ctx.accounts.commission_transaction.dispute_state != DisputeState::None{
+        return err!(CommissionMarketErrors::TransactionDisputed)
+    }
+    ctx.accounts.commission_transaction.dispute_state = DisputeState::Open;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::DisputeOpened)?;
+    Ok(())
+}
+
+/// a buyer deny waits out CONTEST_WINDOW. contesting it in time turns it into a regular
+/// dispute for the arbiter, otherwise anyone can uphold it once the window is over
+pub fn contest_deny_handler(ctx: Context<DisputeParticipant>) -> Result<()>{
+    let tx = &ctx.accounts.commission_transaction;
+    if tx.dispute_state != DisputeState::Denied || ctx.accounts.participant_account.voter_id != tx.metadata.seller{
+        return err!(CommissionMarketErrors::NoDenyToContest)
+    }
+    if Clock::get()?.unix_timestamp > tx.contest_deadline{
+        return err!(CommissionMarketErrors::ContestWindowClosed)
+    }
+    ctx.accounts.commission_transaction.dispute_state = DisputeState::Open;
+    ctx.accounts.commission_transaction.contest_deadline = 0;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::DenyContested)?;
+    Ok(())
+}
+
+pub fn submit_evidence_handler(ctx: Context<DisputeParticipant>, link: String) -> Result<()>{
+    if ctx.accounts.commission_transaction.dispute_state != DisputeState::Open{
+        return err!(CommissionMarketErrors::DisputeNotOpen)
+    }
+    if ctx.accounts.commission_transaction.dispute_evidence.len() >= MAX_EVIDENCE{
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    check_link(&link)?;
+    let submitter = ctx.accounts.participant_account.voter_id;
+    ctx.accounts.commission_transaction.dispute_evidence.push(DisputeEvidence{
+        submitter,
+        link
+    });
+    let rent_payer = RentPayer::of(&ctx.accounts.commission_transaction, ctx.accounts.participant_account.voter_id);
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.wallet.to_account_info(),
+        rent_payer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::EvidenceSubmitted)?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// ARBITRATION
+
+#[derive(Accounts)]
+pub struct RuleDispute<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.dispute_state == DisputeState::Open
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub arbiter: Signer<'info>,
+}
+
+/// seller_rate_bps is the seller's cut of the escrow, the buyer is refunded the rest on close
+pub fn rule_dispute_handler(ctx: Context<RuleDispute>, seller_rate_bps: u16) -> Result<()>{
+    if seller_rate_bps > BPS_DENOMINATOR{
+        return err!(CommissionMarketErrors::InvalidRuling)
+    }
+
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+    ctx.accounts.commission_transaction.close_rate_bps = seller_rate_bps;
+    ctx.accounts.commission_transaction.final_decision = if seller_rate_bps == 0{
+        BuyerDecisionState::Declined
+    }else{
+        BuyerDecisionState::Accept
+    };
+    ctx.accounts.commission_transaction.dispute_state = DisputeState::Ruled;
+    ctx.accounts.commission_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::DisputeRuled{seller_rate_bps})?;
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        system_instruction::transfer
+    }
+};
+use orbit_transaction::transaction_struct::TransactionState;
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    OpenCommissionTransactionSol,
+    OpenInstantSpl,
+    open_commission_sol,
+    open_commission_spl,
+    review_add_ons,
+    delivery_window,
+    CommissionTransition,
+    emit_transition,
+    load_product_terms,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// INSTANT ACCEPT
+/// open, seller accept and fund in one go for products whose seller takes everything,
+/// ordered add-ons included
+
+fn check_instant_accept(product_terms: &AccountInfo) -> Result<()>{
+    match load_product_terms(product_terms)?{
+        Some(terms) if terms.instant_accept => Ok(()),
+        _ => err!(CommissionMarketErrors::InstantAcceptDisabled)
+    }
+}
+
+fn mark_funded(tx: &mut CommissionTransaction) -> Result<()>{
+    review_add_ons(tx, &[])?;
+    tx.metadata.funded = true;
+    tx.metadata.transaction_state = TransactionState::BuyerFunded;
+    tx.delivery_deadline = Clock::get()?.unix_timestamp + delivery_window(tx);
+    Ok(())
+}
+
+pub fn open_instant_sol_handler(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+    check_instant_accept(&ctx.accounts.product_terms)?;
+    open_commission_sol(ctx.accounts, &ctx.bumps, seller_index, buyer_index, price, use_discount, &add_on_ids, buyer_encryption_key)?;
+
+    invoke(
+        &transfer(
+            &ctx.accounts.buyer_wallet.key(),
+            &ctx.accounts.escrow_account.key(),
+            ctx.accounts.commission_transaction.metadata.transaction_price
+        ),
+        &[
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info()
+        ]
+    ).expect("could not fund escrow");
+    mark_funded(&mut ctx.accounts.commission_transaction)?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::InstantAccepted)?;
+    Ok(())
+}
+
+pub fn open_instant_spl_handler(ctx: Context<OpenInstantSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+    check_instant_accept(&ctx.accounts.open.product_terms)?;
+    open_commission_spl(&mut ctx.accounts.open, &ctx.bumps, seller_index, buyer_index, price, use_discount, &add_on_ids, buyer_encryption_key)?;
+
+    let open = &mut ctx.accounts.open;
+    token_interface::fund_escrow(
+        open.token_program.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        open.token_mint.to_account_info(),
+        open.escrow_account.to_account_info(),
+        open.buyer_wallet.to_account_info(),
+        open.commission_transaction.metadata.transaction_price
+    ).expect("could not fund escrow account. maybe check your balance");
+    mark_funded(&mut open.commission_transaction)?;
+    emit_transition(&open.commission_transaction, CommissionTransition::InstantAccepted)?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::{
+    transaction_struct::TransactionState,
+    SellerOpenTransactions
+};
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    MAX_KEYS,
+    fit_transaction,
+    RentPayer,
+    CommissionTransition,
+    emit_transition
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// DELIVERY MANIFEST
+
+/// bitmask with one bit set per key still waiting to be released
+pub fn pending_key_mask(count: usize) -> u64{
+    if count >= 64{
+        u64::MAX
+    }else{
+        (1 << count) - 1
+    }
+}
+
+#[derive(Accounts)]
+pub struct CommitManifest<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded),
+        constraint = !commission_transaction.keys_committed @ CommissionMarketErrors::KeysAlreadyCommitted
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// the seller declares every deliverable once: how many, the hash of each file and the key pda
+/// that will unlock it. committing before funding lets the buyer see the manifest before paying
+pub fn commit_manifest_handler(ctx: Context<CommitManifest>, key_count: u8, content_hashes: Vec<[u8; 32]>, submission_keys: Vec<Pubkey>) -> Result<()>{
+    if key_count as usize > MAX_KEYS{
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    if (content_hashes.len() != key_count as usize) || (submission_keys.len() != key_count as usize){
+        return err!(CommissionMarketErrors::ManifestMismatch)
+    }
+
+    ctx.accounts.commission_transaction.num_keys = pending_key_mask(key_count as usize);
+    ctx.accounts.commission_transaction.key_arr = submission_keys;
+    ctx.accounts.commission_transaction.content_hashes = content_hashes;
+    ctx.accounts.commission_transaction.keys_committed = true;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::ManifestCommitted{key_count})?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// BUYER ENCRYPTION KEY
+
+#[derive(Accounts)]
+pub struct RegisterEncryptionKey<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::Opened) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded),
+        constraint = commission_transaction.sealed_keys.is_empty() @ CommissionMarketErrors::KeyAlreadyReleased
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+}
+
+/// for transactions opened without a key, like accepted quotes, or to rotate the one given at open.
+/// allowed until the first key is sealed to it
+pub fn register_encryption_key_handler(ctx: Context<RegisterEncryptionKey>, encryption_key: [u8; 32]) -> Result<()>{
+    if encryption_key == [0; 32]{
+        return err!(CommissionMarketErrors::NoEncryptionKey)
+    }
+    ctx.accounts.commission_transaction.buyer_encryption_key = encryption_key;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::EncryptionKeyRegistered)?;
+    Ok(())
+}
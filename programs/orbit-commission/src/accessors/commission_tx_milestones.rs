@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::{
+    transaction_struct::TransactionState,
+    SellerOpenTransactions
+};
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    BuyerDecisionState,
+    Milestone,
+    MilestoneState,
+    MAX_MILESTONES,
+    CommitInitData,
+    BuyerDeny,
+    ApproveMilestoneSol,
+    ApproveMilestoneSpl,
+    ensure_bps_rates,
+    CommissionTransition,
+    emit_transition,
+    check_link,
+    fit_transaction,
+    RentPayer,
+    pending_key_mask,
+    Referral,
+    referral_payee,
+    emit_referral,
+    split_escrow,
+    pay_out_sol,
+    pay_out_spl
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// MILESTONE PLANNING
+
+#[derive(Accounts)]
+pub struct SetMilestones<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed,
+        constraint = commission_transaction.milestones.is_empty()
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions: Box<Account<'info, SellerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// has to be set before the buyer funds, funding is the buyer agreeing to the plan
+pub fn set_milestones_handler(ctx: Context<SetMilestones>, amounts: Vec<u64>) -> Result<()>{
+    if amounts.is_empty() || amounts.len() > MAX_MILESTONES{
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    let mut total: u64 = 0;
+    for amt in amounts.iter(){
+        total = total.checked_add(*amt).ok_or(CommissionMarketErrors::MilestoneSumMismatch)?;
+    }
+    if total != ctx.accounts.commission_transaction.metadata.transaction_price{
+        return err!(CommissionMarketErrors::MilestoneSumMismatch)
+    }
+
+    ctx.accounts.commission_transaction.milestones = amounts.into_iter().map(|amt| Milestone{
+        amount: amt,
+        preview_address: String::new(),
+        key_mask: 0,
+        state: MilestoneState::Pending
+    }).collect();
+    ctx.accounts.commission_transaction.milestones_released = 0;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MilestonesSet)?;
+    Ok(())
+}
+
+/// SELLER SUBMITS A MILESTONE
+
+/// also resubmits a declined milestone. key_mask can only name keys that were committed
+pub fn commit_milestone_handler(ctx: Context<CommitInitData>, index: u8, link: String, key_mask: u64) -> Result<()>{
+    check_link(&link)?;
+    let tx = &mut ctx.accounts.commission_transaction;
+    if index as usize >= tx.milestones.len(){
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    if tx.milestones[index as usize].state == MilestoneState::Approved{
+        return err!(CommissionMarketErrors::InvalidMilestoneState)
+    }
+    if key_mask & !pending_key_mask(tx.key_arr.len()) != 0{
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+
+    tx.milestones[index as usize].preview_address = link;
+    tx.milestones[index as usize].key_mask = key_mask;
+    tx.milestones[index as usize].state = MilestoneState::Submitted;
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.seller_wallet.to_account_info(),
+        RentPayer::Seller,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MilestoneCommitted{index})?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// BUYER DECISIONS
+
+fn approve_milestone(tx: &mut CommissionTransaction, index: u8) -> Result<u64>{
+    ensure_bps_rates(tx);
+    if index as usize >= tx.milestones.len(){
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    if tx.milestones[index as usize].state != MilestoneState::Submitted{
+        return err!(CommissionMarketErrors::InvalidMilestoneState)
+    }
+    tx.milestones[index as usize].state = MilestoneState::Approved;
+    let amt = tx.milestones[index as usize].amount;
+    tx.milestones_released += amt;
+
+    if tx.milestones.iter().all(|m| m.state == MilestoneState::Approved){
+        tx.final_decision = BuyerDecisionState::Accept;
+        tx.close_rate = tx.metadata.rate;
+        tx.close_rate_bps = tx.rate_bps;
+        if tx.num_keys == 0{
+            tx.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+        }
+    }
+    Ok(amt)
+}
+
+pub fn approve_milestone_sol_handler<'a>(ctx: Context<'_, '_, '_, 'a, ApproveMilestoneSol<'a>>, index: u8) -> Result<()>{
+    let amt = approve_milestone(&mut ctx.accounts.commission_transaction, index)?;
+
+    let comm_tx = ctx.accounts.commission_transaction.key();
+    let comm_seed = comm_tx.as_ref();
+    let buyer_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_log.as_ref();
+
+    let referral = referral_payee(ctx.remaining_accounts, &ctx.accounts.buyer_account, None)?;
+    let payout = split_escrow(
+        amt,
+        ctx.accounts.commission_transaction.rate_bps,
+        true,
+        ctx.accounts.commission_config.referral_bps,
+        matches!(referral, Referral::Payee(_))
+    );
+
+    if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+        pay_out_sol(
+            &ctx.accounts.escrow_account.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+            &payout,
+            &referral,
+            &ctx.accounts.seller_wallet.to_account_info(),
+            &ctx.accounts.treasury_wallet.to_account_info(),
+            &ctx.accounts.buyer_wallet.to_account_info()
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidEscrowBump)
+    };
+    emit_referral(&ctx.accounts.commission_transaction, &ctx.accounts.buyer_account, &referral, ctx.accounts.treasury_wallet.key(), payout.referral);
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MilestoneApproved{index, amount: amt})?;
+    Ok(())
+}
+
+pub fn approve_milestone_spl_handler<'a>(ctx: Context<'_, '_, '_, 'a, ApproveMilestoneSpl<'a>>, index: u8) -> Result<()>{
+    let amt = approve_milestone(&mut ctx.accounts.commission_transaction, index)?;
+
+    let referral = referral_payee(ctx.remaining_accounts, &ctx.accounts.buyer_account, Some(&ctx.accounts.commission_transaction.metadata.currency))?;
+    let payout = split_escrow(
+        amt,
+        ctx.accounts.commission_transaction.rate_bps,
+        true,
+        ctx.accounts.commission_config.referral_bps,
+        matches!(referral, Referral::Payee(_))
+    );
+
+    if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+        pay_out_spl(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.escrow_account.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.commission_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            &payout,
+            &referral,
+            &ctx.accounts.seller_token_account.to_account_info(),
+            &ctx.accounts.treasury_ata.to_account_info(),
+            &ctx.accounts.buyer_token_account.to_account_info()
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    emit_referral(&ctx.accounts.commission_transaction, &ctx.accounts.buyer_account, &referral, ctx.accounts.treasury_ata.key(), payout.referral);
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MilestoneApproved{index, amount: amt})?;
+    Ok(())
+}
+
+/// declining only sends the milestone back to the seller, who can resubmit it or take
+/// it to a dispute. the rest of the plan and the escrow are untouched
+pub fn decline_milestone_handler(ctx: Context<BuyerDeny>, index: u8) -> Result<()>{
+    let tx = &mut ctx.accounts.commission_transaction;
+    if tx.metadata.transaction_state != TransactionState::BuyerFunded{
+        return err!(CommissionMarketErrors::InvalidMilestoneState)
+    }
+    if index as usize >= tx.milestones.len(){
+        return err!(CommissionMarketErrors::IndexOutOfRange)
+    }
+    if tx.milestones[index as usize].state != MilestoneState::Submitted{
+        return err!(CommissionMarketErrors::InvalidMilestoneState)
+    }
+
+    tx.milestones[index as usize].state = MilestoneState::Declined;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::MilestoneDeclined{index})?;
+    Ok(())
+}
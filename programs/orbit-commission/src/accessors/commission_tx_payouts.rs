@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionTransaction,
+    BuyerDecisionState,
+    DisputeState,
+    BPS_DENOMINATOR,
+    bps_of,
+    Referral,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// PAYOUTS
+/// every escrow payout is computed off the price, never the live balance.
+/// whatever is left in the escrow after the split is rent or transfer fee
+/// overshoot and goes back to the buyer, who put it there
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Payout{
+    pub seller: u64,
+    pub platform: u64,
+    pub referrer: u64,
+    /// refund plus the buyer's half of the referral
+    pub buyer: u64,
+    /// the referrer's cut of the fee. counted in platform when no referrer is paid
+    pub referral: u64,
+}
+
+impl Payout{
+    pub fn total(&self) -> u64{
+        self.seller + self.platform + self.referrer + self.buyer
+    }
+}
+
+/// `seller_bps` of `escrowed` goes to the seller. with `fee_applies` the rest is the
+/// platform fee, out of which referrer and buyer each get `referral_bps` of the escrow.
+/// without it the rest is refunded. the parts always add up to `escrowed`.
+/// the fee or refund is what gets rounded down, the seller keeps the dust
+pub fn split_escrow(escrowed: u64, seller_bps: u16, fee_applies: bool, referral_bps: u16, pay_referrer: bool) -> Payout{
+    let rest = bps_of(escrowed, BPS_DENOMINATOR - seller_bps.min(BPS_DENOMINATOR));
+    let seller = escrowed - rest;
+    if !fee_applies{
+        return Payout{
+            seller,
+            buyer: rest,
+            ..Payout::default()
+        }
+    }
+    // write_config keeps 2 * referral_bps within the fee, the clamp covers configs
+    // written before that check so the referral halves can never exceed the fee
+    let referral = bps_of(escrowed, referral_bps).min(rest / 2);
+    let referrer = if pay_referrer {referral} else {0};
+    Payout{
+        seller,
+        platform: rest.saturating_sub(2 * referrer),
+        referrer,
+        buyer: referrer,
+        referral
+    }
+}
+
+/// what closing pays out. milestones already released are not in the escrow anymore
+pub fn close_payout(tx: &CommissionTransaction, referral_bps: u16, pay_referrer: bool) -> Payout{
+    if !tx.metadata.funded{
+        return Payout::default()
+    }
+    let fee_applies = (tx.close_rate_bps < BPS_DENOMINATOR)
+        && (tx.final_decision == BuyerDecisionState::Accept)
+        && (tx.dispute_state != DisputeState::Ruled);
+    split_escrow(
+        tx.metadata.transaction_price - tx.milestones_released,
+        tx.close_rate_bps,
+        fee_applies,
+        referral_bps,
+        pay_referrer
+    )
+}
+
+/// TRANSFERS
+
+/// pays every part of `payout` out of a SOL escrow. anything left over stays in the escrow
+/// for the caller to sweep
+pub fn pay_out_sol<'info>(
+    escrow: &AccountInfo<'info>,
+    escrow_seeds: &[&[&[u8]]],
+    payout: &Payout,
+    referral: &Referral<'info>,
+    seller: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>
+) -> Result<()>{
+    if let Referral::Payee(reflink_wallet) = referral{
+        orbit_transaction::close_escrow_sol_flat!(
+            escrow.clone(),
+            reflink_wallet.clone(),
+            escrow_seeds,
+            payout.referrer
+        ).expect("couldnt close escrow");
+    }
+    orbit_transaction::close_escrow_sol_flat!(
+        escrow.clone(),
+        treasury.clone(),
+        escrow_seeds,
+        payout.platform
+    ).expect("couldnt close escrow");
+    orbit_transaction::close_escrow_sol_flat!(
+        escrow.clone(),
+        seller.clone(),
+        escrow_seeds,
+        payout.seller
+    ).expect("could not transfer tokens");
+    if payout.buyer > 0{
+        orbit_transaction::close_escrow_sol_flat!(
+            escrow.clone(),
+            buyer.clone(),
+            escrow_seeds,
+            payout.buyer
+        ).expect("couldnt close escrow");
+    }
+    Ok(())
+}
+
+/// pays every part of `payout` out of an SPL escrow owned by `authority`. each part is grossed
+/// up so transfer fee mints still credit the full amount, the funding reserve covers the fee
+#[allow(clippy::too_many_arguments)]
+pub fn pay_out_spl<'info>(
+    token_program: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    authority_seeds: &[&[&[u8]]],
+    payout: &Payout,
+    referral: &Referral<'info>,
+    seller: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>
+) -> Result<()>{
+    let pay = |to: &AccountInfo<'info>, amount: u64| token_interface::transfer_checked(
+        token_program.clone(),
+        escrow.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        authority_seeds,
+        token_interface::gross_up(mint, amount)?
+    );
+    if let Referral::Payee(reflink_token_account) = referral{
+        pay(reflink_token_account, payout.referrer)?;
+    }
+    pay(treasury, payout.platform)?;
+    pay(seller, payout.seller)?;
+    pay(buyer, payout.buyer)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn accept_takes_fee(){
+        let p = split_escrow(100_000_000, 9500, true, 25, false);
+        assert_eq!(p.seller, 95_000_000);
+        assert_eq!(p.platform, 5_000_000);
+        assert_eq!(p.buyer, 0);
+        assert_eq!(p.referral, 250_000);
+        assert_eq!(p.total(), 100_000_000);
+    }
+
+    #[test]
+    fn referral_comes_out_of_the_fee(){
+        let p = split_escrow(100_000_000, 9500, true, 25, true);
+        assert_eq!(p.seller, 95_000_000);
+        assert_eq!(p.referrer, 250_000);
+        assert_eq!(p.buyer, 250_000);
+        assert_eq!(p.platform, 4_500_000);
+        assert_eq!(p.total(), 100_000_000);
+    }
+
+    #[test]
+    fn refund_without_fee(){
+        let p = split_escrow(1_000, 3000, false, 25, true);
+        assert_eq!(p, Payout{seller: 300, buyer: 700, ..Payout::default()});
+    }
+
+    #[test]
+    fn rounding_never_loses_dust(){
+        for escrowed in [0, 1, 2, 3, 7, 99, 101, 9_999, 10_001, 123_456_789, u64::MAX]{
+            for seller_bps in [0, 1, 4999, 9500, 9999, 10000]{
+                for referral_bps in [0, 1, 25, 2500, 5000]{
+                    for (fee, referred) in [(true, true), (true, false), (false, false)]{
+                        let p = split_escrow(escrowed, seller_bps, fee, referral_bps, referred);
+                        assert_eq!(p.total(), escrowed);
+                        assert!(p.referrer * 2 <= escrowed - p.seller);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tiny_escrows(){
+        let p = split_escrow(1, 9500, true, 25, true);
+        assert_eq!(p, Payout{seller: 1, ..Payout::default()});
+        let p = split_escrow(4, 5000, true, 2500, true);
+        assert_eq!(p, Payout{seller: 2, platform: 0, referrer: 1, buyer: 1, referral: 1});
+    }
+
+    #[test]
+    fn seller_keeps_the_rounding(){
+        // 5% of 19 is 0.95, the platform gets 0 and the seller all 19
+        let p = split_escrow(19, 9500, true, 0, false);
+        assert_eq!(p, Payout{seller: 19, ..Payout::default()});
+        let p = split_escrow(39, 9500, true, 0, false);
+        assert_eq!(p, Payout{seller: 38, platform: 1, ..Payout::default()});
+        let p = split_escrow(39, 9500, false, 0, false);
+        assert_eq!(p, Payout{seller: 38, buyer: 1, ..Payout::default()});
+    }
+
+    #[test]
+    fn oversized_referrals_stay_inside_the_fee(){
+        // referral_bps above half the fee, as an unchecked config could hold
+        let p = split_escrow(100_000_000, 9500, true, 4000, true);
+        assert_eq!(p, Payout{seller: 95_000_000, platform: 0, referrer: 2_500_000, buyer: 2_500_000, referral: 2_500_000});
+    }
+
+    #[test]
+    fn seller_share_is_capped(){
+        let p = split_escrow(500, u16::MAX, true, 25, true);
+        assert_eq!(p.seller, 500);
+        assert_eq!(p.total(), 500);
+    }
+}
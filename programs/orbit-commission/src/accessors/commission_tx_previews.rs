@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    MAX_RATE_OFFERS,
+    RateOfferStatus,
+    check_link,
+    fit_transaction,
+    RentPayer,
+    CommissionTransition,
+    emit_transition
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// PREVIEW ROUNDS
+
+/// a preview round can point at a rate offer the seller made and the buyer accepted on this
+/// transaction. each offer pays for one round. offers that fell out of the ring buffer can't be
+/// checked anymore and are refused
+pub fn consume_rate_round(tx: &mut CommissionTransaction, rate_round: u8) -> Result<()>{
+    if rate_round == 0{
+        return Ok(())
+    }
+    if (rate_round > tx.rate_rounds) || ((tx.rate_rounds - rate_round) as usize >= MAX_RATE_OFFERS){
+        return err!(CommissionMarketErrors::InvalidRateRound)
+    }
+    let seller = tx.metadata.seller;
+    match tx.rate_offers.get_mut((rate_round as usize - 1) % MAX_RATE_OFFERS){
+        Some(offer) if offer.proposer == seller && offer.status == RateOfferStatus::Accepted => {
+            offer.status = RateOfferStatus::Consumed;
+            Ok(())
+        },
+        _ => err!(CommissionMarketErrors::InvalidRateRound)
+    }
+}
+
+#[derive(Accounts)]
+pub struct RequestRevision<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// annotates the newest round. `request` links to the buyer's notes for the seller
+pub fn request_revision_handler(ctx: Context<RequestRevision>, version: u8, request: String) -> Result<()>{
+    check_link(&request)?;
+    match ctx.accounts.commission_transaction.preview_rounds.last_mut(){
+        Some(round) if round.version == version => {
+            if !round.revision_request.is_empty(){
+                return err!(CommissionMarketErrors::RevisionAlreadyRequested)
+            }
+            if request.is_empty(){
+                return err!(CommissionMarketErrors::InvalidRevisionRequest)
+            }
+            round.revision_request = request;
+        },
+        _ => return err!(CommissionMarketErrors::PreviewRoundNotFound)
+    }
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.buyer_wallet.to_account_info(),
+        RentPayer::Buyer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RevisionRequested{version})?;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        system_instruction::transfer
+    }
+};
+use orbit_transaction::transaction_struct::TransactionState;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionConfig,
+    CommissionMarketErrors,
+    AcceptPriceSol,
+    AcceptPriceSpl,
+    CommissionTransition,
+    emit_transition,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// PRICE RENEGOTIATION (BuyerFunded only)
+/// proposals are listed prices. they are stored as what the escrow would hold so buyers who
+/// opened with a dispute discount keep paying the discounted amount, and every comparison
+/// with transaction_price happens on that basis
+
+#[derive(Accounts)]
+pub struct ProposePrice<'info>{
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        has_one = wallet,
+        constraint = 
+        (proposer_account.voter_id == commission_transaction.metadata.seller) ||
+        (proposer_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub proposer_account: Account<'info, OrbitMarketAccount>,
+
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+}
+
+/// the escrowed amount for a listed price
+fn escrowed_price(tx: &CommissionTransaction, config: &CommissionConfig, listed: u64) -> Result<u64>{
+    if !tx.used_discount{
+        return Ok(listed)
+    }
+    match listed.checked_mul(config.discount_rate as u64){
+        Some(scaled) => Ok(scaled / 100),
+        None => err!(CommissionMarketErrors::PriceOverflow)
+    }
+}
+
+pub fn propose_price_handler(ctx: Context<ProposePrice>, new_price: u64) -> Result<()>{
+    if !ctx.accounts.commission_transaction.milestones.is_empty(){
+        return err!(CommissionMarketErrors::PriceLockedByMilestones)
+    }
+    let new_price = escrowed_price(&ctx.accounts.commission_transaction, &ctx.accounts.commission_config, new_price)?;
+    if new_price == 0{
+        return err!(CommissionMarketErrors::NoPriceProposal)
+    }
+    // only the buyer can sign for a top up, so increases come from the seller
+    if new_price > ctx.accounts.commission_transaction.metadata.transaction_price &&
+        ctx.accounts.proposer_account.voter_id != ctx.accounts.commission_transaction.metadata.seller
+    {
+        return err!(CommissionMarketErrors::InvalidPriceProposer)
+    }
+    ctx.accounts.commission_transaction.proposed_price = new_price;
+    ctx.accounts.commission_transaction.price_proposer = ctx.accounts.proposer_account.voter_id;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::PriceProposed{price: new_price})?;
+    Ok(())
+}
+
+/// checks the acceptor and returns the new escrowed price
+fn accept_price(tx: &mut CommissionTransaction, acceptor: u64) -> Result<u64>{
+    if tx.metadata.transaction_state != TransactionState::BuyerFunded{
+        return err!(CommissionMarketErrors::WaitingForSellerData)
+    }
+    if tx.proposed_price == 0{
+        return err!(CommissionMarketErrors::NoPriceProposal)
+    }
+    if acceptor == tx.price_proposer{
+        return err!(CommissionMarketErrors::InvalidPriceAcceptor)
+    }
+
+    let new_price = tx.proposed_price;
+    tx.proposed_price = 0;
+    tx.price_proposer = 0;
+    Ok(new_price)
+}
+
+pub fn accept_price_sol_handler(ctx: Context<AcceptPriceSol>) -> Result<()>{
+    let old_price = ctx.accounts.commission_transaction.metadata.transaction_price;
+    let new_price = accept_price(
+        &mut ctx.accounts.commission_transaction,
+        ctx.accounts.acceptor_account.voter_id
+    )?;
+
+    if new_price > old_price{
+        invoke(
+            &transfer(
+                &ctx.accounts.buyer_wallet.key(),
+                &ctx.accounts.escrow_account.key(),
+                new_price - old_price
+            ),
+            &[
+                ctx.accounts.buyer_wallet.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info()
+            ]
+        ).expect("could not top up escrow");
+    }else if new_price < old_price{
+        let comm_tx = ctx.accounts.commission_transaction.key();
+        let comm_seed = comm_tx.as_ref();
+        let buyer_log = ctx.accounts.buyer_transactions_log.key();
+        let buyer_tx_log_seed = buyer_log.as_ref();
+
+        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+            orbit_transaction::close_escrow_sol_flat!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", comm_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                old_price - new_price
+            ).expect("could not refund escrow");
+        }else{
+            return err!(CommissionMarketErrors::InvalidEscrowBump)
+        };
+    }
+
+    ctx.accounts.commission_transaction.metadata.transaction_price = new_price;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::PriceAccepted{price: new_price})?;
+    Ok(())
+}
+
+pub fn accept_price_spl_handler(ctx: Context<AcceptPriceSpl>) -> Result<()>{
+    let old_price = ctx.accounts.commission_transaction.metadata.transaction_price;
+    let new_price = accept_price(
+        &mut ctx.accounts.commission_transaction,
+        ctx.accounts.acceptor_account.voter_id
+    )?;
+
+    if new_price > old_price{
+        token_interface::fund_escrow(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.wallet.to_account_info(),
+            new_price - old_price
+        ).expect("could not top up escrow. maybe check your balance");
+    }else if new_price < old_price{
+        if let Some(auth_bump) = ctx.bumps.get("commission_auth"){
+            // the difference goes back with the part of the funding reserve it no longer needs,
+            // which covers the fee on the way out. the rest of the reserve stays for the payouts
+            let mint = ctx.accounts.token_mint.to_account_info();
+            let released = token_interface::escrow_reserve(&mint, old_price)? - token_interface::escrow_reserve(&mint, new_price)?;
+            let refund = old_price - new_price + released;
+            token_interface::transfer_checked(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.buyer_token_account.to_account_info(),
+                ctx.accounts.commission_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                refund
+            ).expect("could not refund escrow");
+        }else{
+            return err!(CommissionMarketErrors::InvalidAuthBump)
+        };
+    }
+
+    ctx.accounts.commission_transaction.metadata.transaction_price = new_price;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::PriceAccepted{price: new_price})?;
+    Ok(())
+}
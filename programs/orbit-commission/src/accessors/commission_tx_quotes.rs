@@ -0,0 +1,556 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        system_instruction::transfer
+    }
+};
+use orbit_transaction::{
+    transaction_struct::TransactionState,
+    program::OrbitTransaction,
+    TransactionReviews,
+    BuyerOpenTransactions,
+    SellerOpenTransactions
+};
+use orbit_product::CommissionProduct;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionQuote,
+    CommissionConfig,
+    CommissionMarketErrors,
+    BuyerDecisionState,
+    BASE_TRANSACTION_SPACE,
+    BPS_DENOMINATOR,
+    QuoteIssued,
+    CommissionTransition,
+    emit_transition,
+    fit_transaction,
+    RentPayer,
+    product_free_revisions,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// SELLER QUOTES
+
+#[derive(Accounts)]
+#[instruction(buyer: u64)]
+pub struct CreateQuote<'info>{
+    #[account(
+        init,
+        space = 8 + 136,
+        payer = seller_wallet,
+        seeds = [
+            b"commission_quote",
+            commission_product.key().as_ref(),
+            &buyer.to_le_bytes()
+        ],
+        bump
+    )]
+    pub quote: Account<'info, CommissionQuote>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+/// `deadline` is how long the buyer has to accept, `delivery_window` how long the seller
+/// has to deliver once they do
+pub fn create_quote_handler(ctx: Context<CreateQuote>, buyer: u64, price: u64, currency: Pubkey, deadline: i64, delivery_window: i64, scope_hash: [u8; 32]) -> Result<()>{
+    if (price == 0) || (deadline <= Clock::get()?.unix_timestamp) || (delivery_window <= 0){
+        return err!(CommissionMarketErrors::InvalidQuote)
+    }
+    let quote = &mut ctx.accounts.quote;
+    quote.seller = ctx.accounts.seller_market_account.voter_id;
+    quote.buyer = buyer;
+    quote.product = ctx.accounts.commission_product.key();
+    quote.price = price;
+    quote.currency = currency;
+    quote.deadline = deadline;
+    quote.delivery_window = delivery_window;
+    quote.scope_hash = scope_hash;
+
+    emit!(QuoteIssued{
+        quote: quote.key(),
+        product: quote.product,
+        seller: quote.seller,
+        buyer,
+        price,
+        currency,
+        deadline,
+        delivery_window,
+        scope_hash
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawQuote<'info>{
+    #[account(
+        mut,
+        close = seller_wallet,
+        constraint = quote.seller == seller_market_account.voter_id
+    )]
+    pub quote: Account<'info, CommissionQuote>,
+
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+}
+
+pub fn withdraw_quote_handler(_ctx: Context<WithdrawQuote>) -> Result<()>{
+    Ok(())
+}
+
+/// fills in a transaction opened from a quote. it starts out agreed on and funded,
+/// so the seller accept and fund steps never happen
+fn write_quoted_transaction(tx: &mut CommissionTransaction, quote: &CommissionQuote, fee_bps: u16, free_revisions: u8) -> Result<()>{
+    let now = Clock::get()?.unix_timestamp;
+    if quote.deadline <= now{
+        return err!(CommissionMarketErrors::QuoteExpired)
+    }
+    tx.metadata.rate = ((BPS_DENOMINATOR - fee_bps) / 100) as u8;
+    tx.rate_bps = BPS_DENOMINATOR - fee_bps;
+    tx.rates_in_bps = true;
+    tx.metadata.buyer = quote.buyer;
+    tx.metadata.seller = quote.seller;
+    tx.metadata.transaction_state = TransactionState::BuyerFunded;
+    tx.metadata.transaction_price = quote.price;
+    tx.metadata.funded = true;
+    tx.metadata.currency = quote.currency;
+    tx.metadata.reviews = TransactionReviews{
+        buyer: false,
+        seller: false
+    };
+
+    tx.num_keys = 0;
+    tx.final_decision = BuyerDecisionState::Null;
+    tx.free_revisions = free_revisions;
+    tx.delivery_deadline = now.saturating_add(quote.delivery_window);
+    tx.scope_hash = quote.scope_hash;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_quoted_transaction<'info>(
+    transaction_program: AccountInfo<'info>,
+    buyer_account: AccountInfo<'info>,
+    buyer_wallet: AccountInfo<'info>,
+    buyer_log: AccountInfo<'info>,
+    seller_log: AccountInfo<'info>,
+    tx: AccountInfo<'info>,
+    auth_bump: u8,
+    seller_index: u8,
+    buyer_index: u8
+) -> Result<()>{
+    orbit_transaction::cpi::add_buyer_commissions_transaction(
+        CpiContext::new_with_signer(
+            transaction_program.clone(),
+            orbit_transaction::cpi::accounts::AddBuyerCommissionsTransactions{
+                buyer_account,
+                wallet: buyer_wallet,
+                transactions_log: buyer_log,
+                tx: tx.clone()
+            },
+            &[&[b"market_authority", &[auth_bump]]]
+        ),
+        buyer_index
+    )?;
+    orbit_transaction::cpi::add_seller_commissions_transaction(
+        CpiContext::new(
+            transaction_program,
+            orbit_transaction::cpi::accounts::AddSellerCommissionsTransactions{
+                transactions_log: seller_log,
+                tx
+            }
+        ),
+        seller_index
+    )
+}
+
+/// SOL
+
+#[derive(Accounts)]
+#[instruction(seller_tx_index: u8)]
+pub struct AcceptQuoteSol<'info>{
+    #[account(
+        init,
+        payer = buyer_wallet,
+        space = BASE_TRANSACTION_SPACE,
+        seeds = [
+            b"orbit_commission_transaction",
+            seller_transactions_log.key().as_ref(),
+            [seller_tx_index].as_ref()
+        ],
+        bump
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller_wallet,
+        seeds = [
+            b"commission_quote",
+            commission_product.key().as_ref(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        constraint = quote.currency == System::id() @ CommissionMarketErrors::QuoteCurrencyMismatch
+    )]
+    pub quote: Box<Account<'info, CommissionQuote>>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    /// CHECK: seller's CommissionProductTerms, pinned by seeds. may not exist yet
+    #[account(
+        seeds = [
+            b"commission_product_terms",
+            commission_product.key().as_ref()
+        ],
+        bump
+    )]
+    pub product_terms: UncheckedAccount<'info>,
+
+    /// BUYER
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(mut)]
+    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_market_account.voter_id == quote.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// EXTRANEOUS
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn accept_quote_sol_handler(ctx: Context<AcceptQuoteSol>, seller_index: u8, buyer_index: u8) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    write_quoted_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.quote,
+        ctx.accounts.commission_config.fee_bps,
+        product_free_revisions(&ctx.accounts.product_terms)?
+    )?;
+    ctx.accounts.commission_transaction.metadata.product = ctx.accounts.commission_product.metadata.index;
+
+    invoke(
+        &transfer(
+            &ctx.accounts.buyer_wallet.key(),
+            &ctx.accounts.escrow_account.key(),
+            ctx.accounts.quote.price
+        ),
+        &[
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info()
+        ]
+    ).expect("could not fund escrow");
+
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.buyer_wallet.to_account_info(),
+        RentPayer::Buyer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    log_quoted_transaction(
+        ctx.accounts.transaction_program.to_account_info(),
+        ctx.accounts.buyer_market_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        ctx.accounts.buyer_transactions_log.to_account_info(),
+        ctx.accounts.seller_transactions_log.to_account_info(),
+        ctx.accounts.commission_transaction.to_account_info(),
+        *auth_bump,
+        seller_index,
+        buyer_index
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::QuoteAccepted)?;
+    Ok(())
+}
+
+/// SPL
+
+#[derive(Accounts)]
+#[instruction(seller_tx_index: u8)]
+pub struct AcceptQuoteSpl<'info>{
+    #[account(
+        init,
+        payer = buyer_wallet,
+        space = BASE_TRANSACTION_SPACE,
+        seeds = [
+            b"orbit_commission_transaction",
+            seller_transactions_log.key().as_ref(),
+            [seller_tx_index].as_ref()
+        ],
+        bump
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: allocated and initialized by accept_quote_spl under the mint's token program
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller_wallet,
+        seeds = [
+            b"commission_quote",
+            commission_product.key().as_ref(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub quote: Box<Account<'info, CommissionQuote>>,
+
+    /// CHECK: legacy or token-2022 mint the quote is priced in
+    #[account(
+        address = quote.currency @ CommissionMarketErrors::QuoteCurrencyMismatch,
+        constraint = token_interface::is_mint(&token_mint, &token_program.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    /// CHECK: seller's CommissionProductTerms, pinned by seeds. may not exist yet
+    #[account(
+        seeds = [
+            b"commission_product_terms",
+            commission_product.key().as_ref()
+        ],
+        bump
+    )]
+    pub product_terms: UncheckedAccount<'info>,
+
+    /// BUYER
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(mut)]
+    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    /// CHECK: buyer's token account for the quoted mint
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &quote.currency, &buyer_wallet.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_market_account.voter_id == quote.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// EXTRANEOUS
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub fn accept_quote_spl_handler(ctx: Context<AcceptQuoteSpl>, seller_index: u8, buyer_index: u8) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("commission_auth"){
+        auth_bump = ab
+    }else{
+        return err!(CommissionMarketErrors::InvalidAuthBump)
+    };
+    write_quoted_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.quote,
+        ctx.accounts.commission_config.fee_bps,
+        product_free_revisions(&ctx.accounts.product_terms)?
+    )?;
+    ctx.accounts.commission_transaction.metadata.product = ctx.accounts.commission_product.metadata.index;
+
+    let comm_tx = ctx.accounts.commission_transaction.key();
+    let buyer_log = ctx.accounts.buyer_transactions_log.key();
+    if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+        token_interface::create_escrow(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.commission_auth.key(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"orbit_escrow_account", comm_tx.as_ref(), buyer_log.as_ref(), &[*escrow_bump]]]
+        )?;
+    }else{
+        return err!(CommissionMarketErrors::InvalidEscrowBump)
+    };
+
+    token_interface::fund_escrow(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.escrow_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        ctx.accounts.quote.price
+    ).expect("could not fund escrow account. maybe check your balance");
+
+    fit_transaction(
+        &mut ctx.accounts.commission_transaction,
+        &ctx.accounts.buyer_wallet.to_account_info(),
+        RentPayer::Buyer,
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+    log_quoted_transaction(
+        ctx.accounts.transaction_program.to_account_info(),
+        ctx.accounts.buyer_market_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        ctx.accounts.buyer_transactions_log.to_account_info(),
+        ctx.accounts.seller_transactions_log.to_account_info(),
+        ctx.accounts.commission_transaction.to_account_info(),
+        *auth_bump,
+        seller_index,
+        buyer_index
+    )?;
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::QuoteAccepted)?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionTransaction,
+    BPS_DENOMINATOR,
+    CommissionTransition,
+    emit_transition
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// BASIS POINT HELPERS
+
+/// bps share of an amount, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> u64{
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// transactions opened before rates moved to basis points only have the
+/// whole percentage fields filled in. every handler reading a rate calls this first
+pub fn ensure_bps_rates(tx: &mut CommissionTransaction){
+    if tx.rates_in_bps{
+        return
+    }
+    tx.rate_bps = tx.metadata.rate as u16 * 100;
+    tx.preview_rate_bps = tx.preview_rate as u16 * 100;
+    tx.close_rate_bps = tx.close_rate as u16 * 100;
+    tx.rates_in_bps = true;
+}
+
+/// MIGRATION
+
+#[derive(Accounts)]
+pub struct MigrateRates<'info>{
+    #[account(
+        mut,
+        constraint = !commission_transaction.rates_in_bps
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+}
+
+pub fn migrate_rates_handler(ctx: Context<MigrateRates>) -> Result<()>{
+    ensure_bps_rates(&mut ctx.accounts.commission_transaction);
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::RatesMigrated)?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use market_accounts::{
+    OrbitMarketAccount,
+    OrbitReflink
+};
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    ReferralPaid,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// REFERRALS
+/// the referral share of a fee goes half to the buyer, half to the reflink owner.
+/// payouts take [reflink, payee] in remaining_accounts, where payee is the owner's
+/// wallet on SOL and their token account on SPL
+
+pub enum Referral<'info>{
+    /// buyer never used a reflink
+    None,
+    /// buyer has a reflink but it wasn't passed. the referrer's cut stays with the treasury
+    Unclaimed,
+    Payee(AccountInfo<'info>)
+}
+
+/// `currency` is the mint for SPL payouts, None for SOL
+pub fn referral_payee<'info>(remaining_accounts: &[AccountInfo<'info>], buyer_account: &OrbitMarketAccount, currency: Option<&Pubkey>) -> Result<Referral<'info>>{
+    if buyer_account.used_reflink == Pubkey::default(){
+        return Ok(Referral::None)
+    }
+    if remaining_accounts.len() < 2{
+        return Ok(Referral::Unclaimed)
+    }
+    if remaining_accounts[0].key() != buyer_account.used_reflink{
+        return err!(CommissionMarketErrors::InvalidReflink)
+    }
+    let reflink = Account::<OrbitReflink>::try_from(&remaining_accounts[0])
+        .map_err(|_| error!(CommissionMarketErrors::InvalidReflink))?;
+
+    let payee = &remaining_accounts[1];
+    let owned = match currency{
+        None => payee.key() == reflink.reflink_owner,
+        Some(mint) => token_interface::is_token_account(payee, mint, &reflink.reflink_owner)
+    };
+    if !owned{
+        return err!(CommissionMarketErrors::InvalidReferralPayee)
+    }
+    Ok(Referral::Payee(payee.clone()))
+}
+
+pub fn emit_referral(tx: &Account<'_, CommissionTransaction>, buyer_account: &OrbitMarketAccount, referral: &Referral, treasury: Pubkey, amount: u64){
+    if amount == 0{
+        return
+    }
+    let paid_to = match referral{
+        Referral::None => return,
+        Referral::Unclaimed => treasury,
+        Referral::Payee(payee) => payee.key()
+    };
+    emit!(ReferralPaid{
+        transaction: tx.key(),
+        reflink: buyer_account.used_reflink,
+        paid_to,
+        amount
+    });
+}
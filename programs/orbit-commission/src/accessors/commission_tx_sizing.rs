@@ -0,0 +1,101 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        system_instruction::transfer
+    }
+};
+use crate::{
+    CommissionTransaction,
+    CommissionMarketErrors,
+    MAX_LINK_LEN
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// ACCOUNT SIZING
+
+pub fn check_link(link: &str) -> Result<()>{
+    if link.len() > MAX_LINK_LEN{
+        return err!(CommissionMarketErrors::LinkTooLong)
+    }
+    Ok(())
+}
+
+/// bytes the account needs to hold the transaction as it is now
+pub fn transaction_space(tx: &CommissionTransaction) -> Result<usize>{
+    Ok(8 + tx.try_to_vec()?.len())
+}
+
+/// which side of the transaction a realloc is charged to
+#[derive(Clone, Copy, PartialEq)]
+pub enum RentPayer{
+    Buyer,
+    Seller
+}
+
+impl RentPayer{
+    /// for handlers either side can call
+    pub fn of(tx: &CommissionTransaction, voter_id: u64) -> RentPayer{
+        if voter_id == tx.metadata.seller {RentPayer::Seller} else {RentPayer::Buyer}
+    }
+}
+
+/// reallocs the transaction to fit its contents before anchor writes it back.
+/// growing is paid for by `payer`. seller_rent keeps what the seller put in, the buyer paid
+/// the rest, and shrinking only refunds `payer` up to their own share. freed rent that
+/// belongs to the other side stays in the account until close_transaction_account
+pub fn fit_transaction<'info>(
+    tx: &mut Account<'info, CommissionTransaction>,
+    payer: &AccountInfo<'info>,
+    rent_payer: RentPayer,
+    system_program: &AccountInfo<'info>
+) -> Result<()>{
+    let tx_info = tx.to_account_info();
+    let new_len = transaction_space(tx)?;
+    if new_len == tx_info.data_len(){
+        return Ok(())
+    }
+
+    let new_rent = Rent::get()?.minimum_balance(new_len);
+    let lamports = tx_info.lamports();
+    if new_rent > lamports{
+        invoke(
+            &transfer(
+                payer.key,
+                tx_info.key,
+                new_rent - lamports
+            ),
+            &[
+                payer.clone(),
+                tx_info.clone(),
+                system_program.clone()
+            ]
+        )?;
+        if rent_payer == RentPayer::Seller{
+            tx.seller_rent += new_rent - lamports;
+        }
+    }else if new_len < tx_info.data_len(){
+        let share = match rent_payer{
+            RentPayer::Seller => tx.seller_rent,
+            RentPayer::Buyer => lamports.saturating_sub(tx.seller_rent)
+        };
+        let refund = (lamports - new_rent).min(share);
+        **tx_info.try_borrow_mut_lamports()? -= refund;
+        **payer.try_borrow_mut_lamports()? += refund;
+        if rent_payer == RentPayer::Seller{
+            tx.seller_rent -= refund;
+        }
+    }
+    tx_info.realloc(new_len, false)?;
+    Ok(())
+}
+
+/// hands the seller back what they put into the account rent before it is closed.
+/// what is left is the buyer's and goes with the close
+pub fn refund_seller_rent<'info>(tx: &Account<'info, CommissionTransaction>, seller_wallet: &AccountInfo<'info>) -> Result<()>{
+    let tx_info = tx.to_account_info();
+    let seller_rent = tx.seller_rent.min(tx_info.lamports());
+    **tx_info.try_borrow_mut_lamports()? -= seller_rent;
+    **seller_wallet.try_borrow_mut_lamports()? += seller_rent;
+    Ok(())
+}
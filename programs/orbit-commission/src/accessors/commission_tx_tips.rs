@@ -0,0 +1,217 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        system_instruction::transfer
+    }
+};
+use orbit_transaction::transaction_struct::TransactionState;
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    CommissionTransaction,
+    CommissionConfig,
+    CommissionMarketErrors,
+    BuyerDecisionState,
+    bps_of,
+    CommissionTransition,
+    emit_transition,
+    token_interface
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// TIPS
+/// once the buyer has accepted the work they can send the seller extra on top of the
+/// escrowed price, up until the transaction account is closed. tips go wallet to wallet
+/// in the transaction currency, the multisig wallet keeps tip_fee_bps of each one.
+/// the tip total fits in the account's existing space so no rent changes hands
+
+fn record_tip(tx: &mut CommissionTransaction, amount: u64) -> Result<()>{
+    if amount == 0{
+        return err!(CommissionMarketErrors::InvalidTip)
+    }
+    tx.tipped = match tx.tipped.checked_add(amount){
+        Some(total) => total,
+        None => return err!(CommissionMarketErrors::InvalidTip)
+    };
+    Ok(())
+}
+
+/// SOL
+
+#[derive(Accounts)]
+pub struct TipSol<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedProduct) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Closed),
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Accept @ CommissionMarketErrors::InvalidTip,
+        constraint = commission_transaction.metadata.currency == System::id() @ CommissionMarketErrors::InvalidTip
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    #[account(
+        mut,
+        address = commission_config.multisig_wallet
+    )]
+    pub multisig_wallet: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn tip_sol_handler(ctx: Context<TipSol>, amount: u64) -> Result<()>{
+    record_tip(&mut ctx.accounts.commission_transaction, amount)?;
+    let platform_cut = bps_of(amount, ctx.accounts.commission_config.tip_fee_bps);
+
+    invoke(
+        &transfer(
+            &ctx.accounts.buyer_wallet.key(),
+            &ctx.accounts.seller_wallet.key(),
+            amount - platform_cut
+        ),
+        &[
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.seller_wallet.to_account_info()
+        ]
+    )?;
+    if platform_cut > 0{
+        invoke(
+            &transfer(
+                &ctx.accounts.buyer_wallet.key(),
+                &ctx.accounts.multisig_wallet.key(),
+                platform_cut
+            ),
+            &[
+                ctx.accounts.buyer_wallet.to_account_info(),
+                ctx.accounts.multisig_wallet.to_account_info()
+            ]
+        )?;
+    }
+
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Tipped{amount})?;
+    Ok(())
+}
+
+/// SPL
+
+#[derive(Accounts)]
+pub struct TipSpl<'info>{
+    #[account(
+        mut,
+        constraint =    (commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedProduct) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Closed),
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Accept @ CommissionMarketErrors::InvalidTip
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&seller_token_account, &commission_transaction.metadata.currency, &seller_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub seller_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&multisig_token_account, &commission_transaction.metadata.currency, &commission_config.multisig_wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub multisig_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// both transfers are grossed up, the buyer pays the mint's transfer fee on top of the tip
+pub fn tip_spl_handler(ctx: Context<TipSpl>, amount: u64) -> Result<()>{
+    record_tip(&mut ctx.accounts.commission_transaction, amount)?;
+    let platform_cut = bps_of(amount, ctx.accounts.commission_config.tip_fee_bps);
+    let mint = ctx.accounts.token_mint.to_account_info();
+
+    token_interface::transfer_checked(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.seller_token_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        &[],
+        token_interface::gross_up(&mint, amount - platform_cut)?
+    )?;
+    token_interface::transfer_checked(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.multisig_token_account.to_account_info(),
+        ctx.accounts.buyer_wallet.to_account_info(),
+        &[],
+        token_interface::gross_up(&mint, platform_cut)?
+    )?;
+
+    emit_transition(&ctx.accounts.commission_transaction, CommissionTransition::Tipped{amount})?;
+    Ok(())
+}
@@ -1,5 +1,39 @@
-pub mod tx_accessors;
-pub mod commission_tx_common;
-
-pub use tx_accessors::*;
-pub use commission_tx_common::*;
\ No newline at end of file
+pub mod tx_accessors;
+pub mod commission_tx_common;
+pub mod commission_tx_deadlines;
+pub mod commission_tx_milestones;
+pub mod commission_tx_disputes;
+pub mod commission_config_admin;
+pub mod commission_tx_rates;
+pub mod commission_tx_pricing;
+pub mod commission_tx_sizing;
+pub mod commission_tx_manifest;
+pub mod commission_tx_previews;
+pub mod commission_tx_quotes;
+pub mod commission_product_terms;
+pub mod commission_tx_instant;
+pub mod commission_tx_cancel;
+pub mod commission_tx_referrals;
+pub mod commission_tx_payouts;
+pub mod commission_tx_tips;
+pub mod commission_tx_add_ons;
+
+pub use tx_accessors::*;
+pub use commission_tx_common::*;
+pub use commission_tx_deadlines::*;
+pub use commission_tx_milestones::*;
+pub use commission_tx_disputes::*;
+pub use commission_config_admin::*;
+pub use commission_tx_rates::*;
+pub use commission_tx_pricing::*;
+pub use commission_tx_sizing::*;
+pub use commission_tx_manifest::*;
+pub use commission_tx_previews::*;
+pub use commission_tx_quotes::*;
+pub use commission_product_terms::*;
+pub use commission_tx_instant::*;
+pub use commission_tx_cancel::*;
+pub use commission_tx_referrals::*;
+pub use commission_tx_payouts::*;
+pub use commission_tx_tips::*;
+pub use commission_tx_add_ons::*;
@@ -1,393 +1,675 @@
-use anchor_lang::prelude::*;
-use market_accounts::{
-    OrbitMarketAccount,
-    program::OrbitMarketAccounts
-};
-use orbit_product::program::OrbitProduct;
-use crate::{
-    CommissionTransaction,
-    BuyerDecisionState, program::OrbitCommissionMarket,
-};
-use orbit_transaction::{transaction_struct::TransactionState, BuyerOpenTransactions, SellerOpenTransactions, program::OrbitTransaction};
-use orbit_product::CommissionProduct;
-use anchor_spl::token::{
-    TokenAccount,
-    Mint,
-    Token
-};
-
-#[derive(Accounts)]
-#[instruction(seller_tx_index: u8)]
-pub struct OpenCommissionTransactionSpl<'info>{
-    //////////////////////////////////
-    /// TX
-    #[account(
-        init,
-        space = 2400,
-        payer = buyer_wallet,
-        seeds = [
-            b"orbit_commission_transaction",
-            seller_transactions_log.key().as_ref(),
-            [seller_tx_index].as_ref()
-        ],
-        bump
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-
-    #[account(
-        init,
-        token::mint = token_mint,
-        token::authority = commission_auth,
-        seeds = [
-            b"orbit_escrow_account",
-            commission_transaction.key().as_ref(),
-            buyer_transactions_log.key().as_ref()
-        ],
-        bump,
-        payer = buyer_wallet
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    //////////////////////////////////
-    /// PRODUCT
-    
-    pub token_mint: Account<'info, Mint>,
-
-    #[account(
-        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
-    )]
-    pub commission_product: Box<Account<'info, CommissionProduct>>,
-    
-    //////////////////////////////////////////////////
-    /// BUYER SELLER
-    
-    /// BUYER
-    #[account(
-        mut,
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
-
-    #[account(
-        mut,
-        constraint = buyer_market_account.wallet == buyer_wallet.key()
-    )]
-    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
-    
-    #[account(mut)]
-    pub buyer_wallet: Signer<'info>,
-    
-    /// SELLER
-
-    #[account(    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
-
-    //////////////////////////////////////////////////
-    /// EXTRANEOUS CPI
-    #[account(
-        seeds = [b"market_authority"],
-        bump
-    )]
-    pub commission_auth: SystemAccount<'info>,
-
-    pub commission_program: Program<'info, OrbitCommissionMarket>,
-
-    pub market_account_program: Program<'info, OrbitMarketAccounts>,
-
-    pub system_program: Program<'info, System>,
-
-    pub token_program: Program<'info, Token>,
-
-    pub product_program: Program<'info, OrbitProduct>,
-    
-    pub transaction_program: Program<'info, OrbitTransaction>,
-
-    pub rent: Sysvar<'info, Rent>
-}
-
-
-#[derive(Accounts)]
-pub struct CloseCommissionTransactionSpl<'info>{
-    //////////////////////////////////
-    /// TX
-    #[account(
-        mut,
-        constraint =    ((commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedProduct) && (commission_transaction.final_decision != BuyerDecisionState::Null)) ||
-                        (commission_transaction.metadata.transaction_state == TransactionState::Opened),
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-    
-    #[account(
-        mut,
-        constraint = commission_product.metadata.index == commission_transaction.metadata.product,
-        constraint = commission_product.metadata.owner_catalog == seller_account.voter_id
-    )] 
-    pub commission_product: Box<Account<'info, CommissionProduct>>,
-    
-    #[account(
-        mut,
-        seeds = [
-            b"orbit_escrow_account",
-            commission_transaction.key().as_ref(),
-            buyer_transactions_log.key().as_ref()
-        ],
-        bump
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    //////////////////////////////////
-    /// BUYER SELLER
-    
-    /// BUYER
-    #[account(
-        mut,
-        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
-    )]
-    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
-
-    #[account(
-        mut,
-        token::authority = buyer_account.wallet
-    )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-
-    /// SELLER
-    #[account(
-        mut,
-        constraint = seller_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        mut,
-        token::authority = seller_account.wallet
-    )]
-    pub seller_token_account: Account<'info, TokenAccount>,
-
-    
-    //////////////////////////////////
-    /// CPI AND EXTRANEOUS
-    
-    #[account(
-        seeds = [b"market_authority"],
-        bump
-    )]
-    pub commission_auth: SystemAccount<'info>,
-    
-    #[account(
-        token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
-    )]
-    pub multisig_ata: Account<'info, TokenAccount>,
-
-    pub market_account_program: Program<'info, OrbitMarketAccounts>,
-    
-    pub commission_program: Program<'info, OrbitCommissionMarket>,
-
-    pub transaction_program: Program<'info, OrbitTransaction>,
-
-    pub token_program: Program<'info, Token>,
-    
-    pub product_program: Program<'info, OrbitProduct>,
-    
-}
-
-#[derive(Accounts)]
-pub struct FundEscrowSpl<'info>{
-    ////////////////////////////////////////////
-    /// TX
-    #[account(
-        mut,
-        constraint = commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed,
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-    
-    #[account(
-        mut,
-        seeds = [
-            b"orbit_escrow_account",
-            commission_transaction.key().as_ref(),
-            buyer_transactions_log.key().as_ref()
-        ],
-        bump
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    ////////////////////////////////////////////
-    /// BUYER SELLER
-    
-    /// BUYER
-    
-    #[account(
-        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
-    )]
-    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
-
-    #[account(
-        mut,
-        token::authority = buyer_wallet.key()
-    )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-
-    #[account(
-        address = buyer_market_account.wallet
-    )]
-    pub buyer_wallet: Signer<'info>,
-
-    //////////////////////////////////
-    /// CPI AND EXTRANEOUS
-
-    pub token_program: Program<'info, Token>
-}
-
-#[derive(Accounts)]
-pub struct SellerEarlyDeclineSpl<'info>{
-    //////////////////////////////////
-    /// TX
-    #[account(
-        mut,
-        constraint = commission_transaction.final_decision == BuyerDecisionState::Null
-    )]
-    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
-    
-    #[account(
-        mut,
-        seeds = [
-            b"orbit_escrow_account",
-            commission_transaction.key().as_ref(),
-            buyer_transactions_log.key().as_ref()
-        ],
-        bump
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    //////////////////////////////////
-    /// BUYER SELLER
-    
-    /// BUYER
-    #[account(
-        mut,
-        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
-    )]
-    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &buyer_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
-
-    #[account(
-        mut,
-        token::authority = buyer_account.wallet
-    )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-
-    /// SELLER
-
-    #[account(
-        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
-    )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
-            &seller_market_account.voter_id.to_le_bytes()
-        ], 
-        bump,
-        seeds::program = &orbit_transaction::id()
-    )]
-    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
-
-    #[account(
-        mut,
-        token::authority = seller_wallet
-    )]
-    pub seller_token_account: Account<'info, TokenAccount>,
-
-    #[account(
-        address = seller_market_account.wallet
-    )]
-    pub seller_wallet: Signer<'info>,
-
-    
-    //////////////////////////////////
-    /// CPI AND EXTRANEOUS
-    
-    #[account(
-        seeds = [b"market_authority"],
-        bump
-    )]
-    pub commission_auth: SystemAccount<'info>,
-
-    pub market_account_program: Program<'info, OrbitMarketAccounts>,
-    
-    pub commission_program: Program<'info, OrbitCommissionMarket>,
-
-    pub transaction_program: Program<'info, OrbitTransaction>,
-
-    pub token_program: Program<'info, Token>,
-    
-}
\ No newline at end of file
+use anchor_lang::prelude::*;
+use market_accounts::{
+    OrbitMarketAccount,
+    program::OrbitMarketAccounts
+};
+use orbit_product::program::OrbitProduct;
+use crate::{
+    CommissionTransaction,
+    CommissionConfig,
+    BASE_TRANSACTION_SPACE,
+    BuyerDecisionState, program::OrbitCommissionMarket,
+    CommissionMarketErrors,
+    DisputeState,
+    token_interface
+};
+use orbit_transaction::{transaction_struct::TransactionState, BuyerOpenTransactions, SellerOpenTransactions, program::OrbitTransaction};
+use orbit_product::CommissionProduct;
+
+#[derive(Accounts)]
+#[instruction(seller_tx_index: u8)]
+pub struct OpenCommissionTransactionSpl<'info>{
+    //////////////////////////////////
+    /// TX
+    #[account(
+        init,
+        space = BASE_TRANSACTION_SPACE,
+        payer = buyer_wallet,
+        seeds = [
+            b"orbit_commission_transaction",
+            seller_transactions_log.key().as_ref(),
+            [seller_tx_index].as_ref()
+        ],
+        bump
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: allocated and initialized by open_spl under the mint's token program
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// PRODUCT
+    
+    /// CHECK: legacy or token-2022 mint
+    #[account(
+        constraint = token_interface::is_mint(&token_mint, &token_program.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = commission_product.metadata.owner_catalog == seller_market_account.voter_id
+    )]
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+
+    /// CHECK: seller's CommissionProductTerms, pinned by seeds. may not exist yet
+    #[account(
+        seeds = [
+            b"commission_product_terms",
+            commission_product.key().as_ref()
+        ],
+        bump
+    )]
+    pub product_terms: UncheckedAccount<'info>,
+    
+    //////////////////////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        constraint = buyer_market_account.wallet == buyer_wallet.key()
+    )]
+    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(mut)]
+    pub buyer_wallet: Signer<'info>,
+    
+    /// SELLER
+
+    #[account(    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    //////////////////////////////////////////////////
+    /// EXTRANEOUS CPI
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+    
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub rent: Sysvar<'info, Rent>
+}
+
+
+#[derive(Accounts)]
+pub struct CloseCommissionTransactionSpl<'info>{
+    //////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint =    ((commission_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedProduct) && (commission_transaction.final_decision != BuyerDecisionState::Null)) ||
+                        (commission_transaction.metadata.transaction_state == TransactionState::Opened),
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+    
+    #[account(
+        mut,
+        constraint = commission_product.metadata.index == commission_transaction.metadata.product,
+        constraint = commission_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub commission_product: Box<Account<'info, CommissionProduct>>,
+    
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    /// gets the escrow's rent back
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+    #[account(
+        mut,
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&seller_token_account, &commission_transaction.metadata.currency, &seller_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub seller_token_account: UncheckedAccount<'info>,
+
+    
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&treasury_ata, &commission_transaction.metadata.currency, &commission_config.treasury) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub treasury_ata: UncheckedAccount<'info>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+    
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+    
+    pub product_program: Program<'info, OrbitProduct>,
+    
+}
+
+#[derive(Accounts)]
+pub struct FundEscrowSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::SellerConfirmed,
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+    
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    ////////////////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    
+    #[account(
+        constraint = buyer_market_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_wallet.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>
+}
+
+#[derive(Accounts)]
+pub struct SellerEarlyDeclineSpl<'info>{
+    //////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+    
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    /// gets the escrow rent back
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+
+    #[account(
+        constraint = seller_market_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &seller_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&seller_token_account, &commission_transaction.metadata.currency, &seller_wallet.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub seller_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+    
+    pub commission_program: Program<'info, OrbitCommissionMarket>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+    
+}
+#[derive(Accounts)]
+pub struct ApproveMilestoneSpl<'info>{
+    //////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded,
+        constraint = commission_transaction.final_decision == BuyerDecisionState::Null,
+        constraint = commission_transaction.dispute_state == DisputeState::None @ CommissionMarketErrors::TransactionDisputed
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == commission_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&seller_token_account, &commission_transaction.metadata.currency, &seller_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub seller_token_account: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [b"commission_config"],
+        bump
+    )]
+    pub commission_config: Box<Account<'info, CommissionConfig>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&treasury_ata, &commission_transaction.metadata.currency, &commission_config.treasury) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub treasury_ata: UncheckedAccount<'info>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// the regular open accounts plus the buyer's token account to fund from
+#[derive(Accounts)]
+pub struct OpenInstantSpl<'info>{
+    pub open: OpenCommissionTransactionSpl<'info>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &open.token_mint.key(), &open.buyer_wallet.key()) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptPriceSpl<'info>{
+    //////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = commission_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub commission_transaction: Box<Account<'info, CommissionTransaction>>,
+
+    /// CHECK: escrow token account, pinned by seeds
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            commission_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the transaction currency
+    #[account(
+        address = commission_transaction.metadata.currency
+    )]
+    pub token_mint: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// ACCEPTOR
+
+    #[account(
+        has_one = wallet,
+        constraint = 
+        (acceptor_account.voter_id == commission_transaction.metadata.seller) ||
+        (acceptor_account.voter_id == commission_transaction.metadata.buyer)
+    )]
+    pub acceptor_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    pub wallet: Signer<'info>,
+
+    //////////////////////////////////
+    /// BUYER
+
+    #[account(
+        constraint = buyer_account.voter_id == commission_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    /// CHECK: token account of the transaction currency, under either token program
+    #[account(
+        mut,
+        constraint = token_interface::is_token_account(&buyer_token_account, &commission_transaction.metadata.currency, &buyer_account.wallet) @ CommissionMarketErrors::InvalidTokenAccount
+    )]
+    pub buyer_token_account: UncheckedAccount<'info>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub commission_auth: SystemAccount<'info>,
+
+    /// CHECK: spl token or token-2022
+    #[account(
+        constraint = token_interface::is_token_program(&token_program.key()) @ CommissionMarketErrors::InvalidTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
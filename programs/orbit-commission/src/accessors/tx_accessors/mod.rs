@@ -1,5 +1,5 @@
-pub mod commission_tx_sol;
-pub mod commission_tx_spl;
-
-pub use commission_tx_sol::*;
+pub mod commission_tx_sol;
+pub mod commission_tx_spl;
+
+pub use commission_tx_sol::*;
 pub use commission_tx_spl::*;
\ No newline at end of file
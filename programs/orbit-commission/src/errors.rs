@@ -1,31 +1,125 @@
-use anchor_lang::prelude::*;
-
-#[error_code]
-pub enum CommissionMarketErrors{
-    #[msg("Wrong account to pay")]
-    WrongDecisionAccount,
-    #[msg("Decision has not been made yet, can not close")]
-    UndecidedTransaction,
-    #[msg("Can not accept your own rate")]
-    InvalidRateAcceptor,
-    #[msg("Please confirm delivery first")]
-    DidNotConfirmDelivery,
-    #[msg("The seller did not commit keys yet")]
-    WaitingForSellerData,
-    #[msg("The seller for the product does not match the seller given")]
-    InvalidSellerForListing,
-    #[msg("Could not compute escrow bump")]
-    InvalidEscrowBump,
-    #[msg("Could not compute auth bump")]
-    InvalidAuthBump,
-    #[msg("Could not decode private key")]
-    CorruptPrivateKeyFormat,
-    #[msg("Private and Public keys do not match")]
-    IncorrectPrivateKey,
-    #[msg("Private and Public keys do not match")]
-    IndexOutOfRange,
-    #[msg("Can not use discounts on commissions")]
-    CannotDiscountCommission,
-    #[msg("invalid reflink passed")]
-    InvalidReflink,
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum CommissionMarketErrors{
+    #[msg("Wrong account to pay")]
+    WrongDecisionAccount,
+    #[msg("Decision has not been made yet, can not close")]
+    UndecidedTransaction,
+    #[msg("Can not accept your own rate")]
+    InvalidRateAcceptor,
+    #[msg("Please confirm delivery first")]
+    DidNotConfirmDelivery,
+    #[msg("The seller did not commit keys yet")]
+    WaitingForSellerData,
+    #[msg("The seller for the product does not match the seller given")]
+    InvalidSellerForListing,
+    #[msg("Could not compute escrow bump")]
+    InvalidEscrowBump,
+    #[msg("Could not compute auth bump")]
+    InvalidAuthBump,
+    #[msg("Could not decode private key")]
+    CorruptPrivateKeyFormat,
+    #[msg("Private and Public keys do not match")]
+    IncorrectPrivateKey,
+    #[msg("Private and Public keys do not match")]
+    IndexOutOfRange,
+    #[msg("Can not use discounts on commissions")]
+    CannotDiscountCommission,
+    #[msg("invalid reflink passed")]
+    InvalidReflink,
+    #[msg("The deadline for this stage has not passed yet")]
+    DeadlineNotReached,
+    #[msg("Milestone amounts must add up to the transaction price")]
+    MilestoneSumMismatch,
+    #[msg("Milestone is not in the right state for this action")]
+    InvalidMilestoneState,
+    #[msg("Transaction is under dispute")]
+    TransactionDisputed,
+    #[msg("There is no open dispute on this transaction")]
+    DisputeNotOpen,
+    #[msg("Ruling must give the seller between 0 and 10000 basis points")]
+    InvalidRuling,
+    #[msg("Fee, referral share or discount out of range")]
+    InvalidConfig,
+    #[msg("Rate must be between 0 and 10000 basis points")]
+    InvalidRate,
+    #[msg("No pending rate offer")]
+    NoPendingRateOffer,
+    #[msg("Rate negotiation has run out of rounds")]
+    RateRoundsExhausted,
+    #[msg("Rate offer note is too long")]
+    RateNoteTooLong,
+    #[msg("No price change has been proposed")]
+    NoPriceProposal,
+    #[msg("Price increases have to be proposed by the seller and accepted by the buyer")]
+    InvalidPriceProposer,
+    #[msg("Can not accept your own price")]
+    InvalidPriceAcceptor,
+    #[msg("Price can not change once milestones are set")]
+    PriceLockedByMilestones,
+    #[msg("Token account has the wrong mint, owner or token program")]
+    InvalidTokenAccount,
+    #[msg("Token program must be spl token or token-2022")]
+    InvalidTokenProgram,
+    #[msg("Link is longer than the allowed maximum")]
+    LinkTooLong,
+    #[msg("Keys were already committed for this transaction")]
+    KeysAlreadyCommitted,
+    #[msg("Key count, content hashes and key addresses must line up")]
+    ManifestMismatch,
+    #[msg("Buyer has not registered an encryption key")]
+    NoEncryptionKey,
+    #[msg("Sealed key is missing or too long")]
+    InvalidSealedKey,
+    #[msg("Key was already released")]
+    KeyAlreadyReleased,
+    #[msg("No more preview rounds can be posted")]
+    PreviewRoundsExhausted,
+    #[msg("Free revisions are used up, the round needs a rate proposal")]
+    PaidRevisionNeedsRate,
+    #[msg("Rate round is not an accepted, unused seller offer on this transaction")]
+    InvalidRateRound,
+    #[msg("Revisions can only be requested on the newest preview round")]
+    PreviewRoundNotFound,
+    #[msg("Revision was already requested for this round")]
+    RevisionAlreadyRequested,
+    #[msg("Revision request can't be empty")]
+    InvalidRevisionRequest,
+    #[msg("Quote needs a price and a deadline in the future")]
+    InvalidQuote,
+    #[msg("Quote deadline has passed")]
+    QuoteExpired,
+    #[msg("Quote is priced in a different currency")]
+    QuoteCurrencyMismatch,
+    #[msg("Product terms are inconsistent")]
+    InvalidProductTerms,
+    #[msg("Price is below the product's listed minimum")]
+    PriceBelowListing,
+    #[msg("Product is not listed in this currency")]
+    CurrencyNotAccepted,
+    #[msg("Product does not allow instant accept")]
+    InstantAcceptDisabled,
+    #[msg("No matching cancel offer to accept")]
+    NoCancelOffer,
+    #[msg("Can not accept your own cancel offer")]
+    InvalidCancelAcceptor,
+    #[msg("Referral payout account does not belong to the reflink owner")]
+    InvalidReferralPayee,
+    #[msg("Only commissions that were completed can be reviewed")]
+    ReviewNotEligible,
+    #[msg("The review window for this commission has closed")]
+    ReviewWindowClosed,
+    #[msg("Tip has to be in the transaction currency and more than zero")]
+    InvalidTip,
+    #[msg("Add-on is not offered on this product or was not ordered")]
+    AddOnNotListed,
+    #[msg("Only the seller can contest a pending buyer deny")]
+    NoDenyToContest,
+    #[msg("The window to contest this deny has closed")]
+    ContestWindowClosed,
+    #[msg("Price is too large")]
+    PriceOverflow,
+    #[msg("Price has to match the product's listed price in this currency")]
+    PriceNotListed,
 }
\ No newline at end of file
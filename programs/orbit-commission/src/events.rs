@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use crate::CommissionTransaction;
+
+/// what happened to the transaction. anything a step moved or settled on rides in
+/// the variant, everything else can be read off the event itself
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum CommissionTransition{
+    Opened,
+    SellerAccepted,
+    Funded,
+    PreviewCommitted{version: u8, rate_round: u8},
+    RateProposed{round: u8, rate_bps: u16},
+    RateAccepted{round: u8},
+    RateRejected{round: u8},
+    RateWithdrawn{round: u8},
+    PriceProposed{price: u64},
+    PriceAccepted{price: u64},
+    /// pending_keys is the mask of keys still to be released
+    KeysCommitted{pending_keys: u64},
+    LinkCommitted,
+    Shipped,
+    Delivered,
+    Accepted,
+    Denied,
+    SubkeysReleased{pending_keys: u64},
+    Closed,
+    EarlyDeclined,
+    Reviewed{rating: u8},
+    DeliveryExpired,
+    ConfirmationExpired,
+    MilestonesSet,
+    MilestoneCommitted{index: u8},
+    MilestoneApproved{index: u8, amount: u64},
+    MilestoneDeclined{index: u8},
+    DisputeOpened,
+    EvidenceSubmitted,
+    DisputeRuled{seller_rate_bps: u16},
+    RatesMigrated,
+    ManifestCommitted{key_count: u8},
+    EncryptionKeyRegistered,
+    RevisionRequested{version: u8},
+    QuoteAccepted,
+    InstantAccepted,
+    BuyerCancelled,
+    MutualCancelProposed{seller_bps: u16},
+    MutualCancelled{seller: u64, buyer: u64},
+    Tipped{amount: u64},
+    /// amount is the add-ons' share of the price
+    AddOnsSelected{amount: u64},
+    DenyContested,
+    DenyUpheld,
+    RateExpired{round: u8}
+}
+
+/// every commission handler emits one of these.
+/// price, rates and state are the transaction's after the step
+#[event]
+pub struct CommissionTransitionEvent{
+    pub transaction: Pubkey,
+    pub transition: CommissionTransition,
+    pub buyer: u64,
+    pub seller: u64,
+    pub price: u64,
+    pub preview_rate_bps: u16,
+    pub close_rate_bps: u16,
+    pub state: TransactionState,
+    pub timestamp: i64,
+}
+
+/// one per key released through commit_subkeys, so the buyer can open the
+/// sealed key and check the decrypted file against what the seller committed to
+#[event]
+pub struct DeliverableReleased{
+    pub transaction: Pubkey,
+    pub index: u8,
+    pub ciphertext: Vec<u8>,
+    pub content_hash: [u8; 32],
+}
+
+/// a seller quote is waiting on the buyer. there is no transaction yet so this
+/// is the only way the buyer's client learns about it
+#[event]
+pub struct QuoteIssued{
+    pub quote: Pubkey,
+    pub product: Pubkey,
+    pub seller: u64,
+    pub buyer: u64,
+    pub price: u64,
+    pub currency: Pubkey,
+    pub deadline: i64,
+    pub delivery_window: i64,
+    pub scope_hash: [u8; 32],
+}
+
+/// the referrer's cut of a fee. paid_to is the treasury when the payout
+/// was cranked without the reflink accounts
+#[event]
+pub struct ReferralPaid{
+    pub transaction: Pubkey,
+    pub reflink: Pubkey,
+    pub paid_to: Pubkey,
+    pub amount: u64,
+}
+
+/// written reviews live off chain, the hash ties them to the rating
+#[event]
+pub struct ReviewSubmitted{
+    pub transaction: Pubkey,
+    pub reviewer: u64,
+    pub reviewed: u64,
+    pub rating: u8,
+    pub review_hash: [u8; 32],
+}
+
+pub fn emit_transition(tx: &Account<'_, CommissionTransaction>, transition: CommissionTransition) -> Result<()>{
+    emit!(CommissionTransitionEvent{
+        transaction: tx.key(),
+        transition,
+        buyer: tx.metadata.buyer,
+        seller: tx.metadata.seller,
+        price: tx.metadata.transaction_price,
+        preview_rate_bps: tx.preview_rate_bps,
+        close_rate_bps: tx.close_rate_bps,
+        state: tx.metadata.transaction_state,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
@@ -3,10 +3,13 @@ use anchor_lang::prelude::*;
 pub mod accessors;
 pub mod structs;
 pub mod errors;
+pub mod events;
+pub mod token_interface;
 
 pub use accessors::*;
 pub use structs::*;
 pub use errors::*;
+pub use events::*;
 
 // use crate::orbit_product::*;
 // use crate::transactions::*;
@@ -23,8 +26,8 @@ pub mod orbit_commission_market {
     /// TRANSACTION
 
     /// SOL
-    pub fn open_transaction_sol(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
-        CommissionTransaction::open_sol(ctx, seller_index, buyer_index, price, use_discount)
+    pub fn open_transaction_sol(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+        open_commission_sol(ctx.accounts, &ctx.bumps, seller_index, buyer_index, price, use_discount, &add_on_ids, buyer_encryption_key)
     }
 
     pub fn close_transaction_sol<'a>(ctx: Context<'_, '_, '_, 'a, CloseCommissionTransactionSol<'a>>) -> Result<()>{
@@ -39,9 +42,21 @@ pub mod orbit_commission_market {
         CommissionTransaction::seller_early_decline_sol(ctx)
     }
 
+    pub fn open_instant_sol(ctx: Context<OpenCommissionTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+        open_instant_sol_handler(ctx, seller_index, buyer_index, price, use_discount, add_on_ids, buyer_encryption_key)
+    }
+
+    pub fn buyer_cancel_sol(ctx: Context<BuyerCancelSol>) -> Result<()>{
+        buyer_cancel_sol_handler(ctx)
+    }
+
+    pub fn accept_mutual_cancel_sol(ctx: Context<MutualCancelSol>, seller_bps: u16) -> Result<()>{
+        accept_mutual_cancel_sol_handler(ctx, seller_bps)
+    }
+
     /// SPL
-    pub fn open_transaction_spl(ctx: Context<OpenCommissionTransactionSpl>, seller_index: u8, buyer_index: u8,  price: u64, use_discount: bool) -> Result<()>{
-        CommissionTransaction::open_spl(ctx, seller_index, buyer_index, price, use_discount)
+    pub fn open_transaction_spl(ctx: Context<OpenCommissionTransactionSpl>, seller_index: u8, buyer_index: u8,  price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+        open_commission_spl(ctx.accounts, &ctx.bumps, seller_index, buyer_index, price, use_discount, &add_on_ids, buyer_encryption_key)
     }
 
     pub fn close_transaction_spl<'a>(ctx: Context<'_, '_, '_, 'a, CloseCommissionTransactionSpl<'a>>) -> Result<()>{
@@ -56,10 +71,26 @@ pub mod orbit_commission_market {
         CommissionTransaction::seller_early_decline_spl(ctx)
     }
 
+    pub fn open_instant_spl(ctx: Context<OpenInstantSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, add_on_ids: Vec<u8>, buyer_encryption_key: [u8; 32]) -> Result<()>{
+        open_instant_spl_handler(ctx, seller_index, buyer_index, price, use_discount, add_on_ids, buyer_encryption_key)
+    }
+
+    pub fn buyer_cancel_spl(ctx: Context<BuyerCancelSpl>) -> Result<()>{
+        buyer_cancel_spl_handler(ctx)
+    }
+
+    pub fn accept_mutual_cancel_spl(ctx: Context<MutualCancelSpl>, seller_bps: u16) -> Result<()>{
+        accept_mutual_cancel_spl_handler(ctx, seller_bps)
+    }
+
     /// COMMON
     pub fn close_transaction_account(ctx: Context<CloseTransactionAccount>) -> Result<()>{
         CommissionTransaction::close_transaction_account(ctx)
     }
+
+    pub fn propose_mutual_cancel(ctx: Context<ProposeMutualCancel>, seller_bps: u16) -> Result<()>{
+        propose_mutual_cancel_handler(ctx, seller_bps)
+    }
     
     /// BUYER UTILS
     pub fn confirm_delivered(ctx: Context<BuyerConfirmation>) -> Result<()>{
@@ -79,6 +110,14 @@ pub mod orbit_commission_market {
         commit_init_keys_handler(ctx, submission_keys)
     }
 
+    pub fn commit_manifest(ctx: Context<CommitManifest>, key_count: u8, content_hashes: Vec<[u8; 32]>, submission_keys: Vec<Pubkey>) -> Result<()>{
+        commit_manifest_handler(ctx, key_count, content_hashes, submission_keys)
+    }
+
+    pub fn register_encryption_key(ctx: Context<RegisterEncryptionKey>, encryption_key: [u8; 32]) -> Result<()>{
+        register_encryption_key_handler(ctx, encryption_key)
+    }
+
     pub fn commit_link(ctx: Context<CommitInitData>, link: String) -> Result<()>{
         commit_link_handler(ctx, link)
     }
@@ -87,28 +126,156 @@ pub mod orbit_commission_market {
         update_status_to_shipping_handler(ctx)
     }
 
-    pub fn commit_subkeys(ctx: Context<CommitSubKeys>, indexes: Vec<u8>) -> Result<()>{
-        commit_subkeys_handler(ctx, indexes)
+    pub fn commit_subkeys(ctx: Context<CommitSubKeys>, indexes: Vec<u8>, ciphertexts: Vec<Vec<u8>>) -> Result<()>{
+        commit_subkeys_handler(ctx, indexes, ciphertexts)
     }
 
-    pub fn seller_accept_transaction(ctx: Context<SellerAcceptTransaction>) -> Result<()>{
-        seller_accept_transaction_handler(ctx)
+    pub fn seller_accept_transaction(ctx: Context<SellerAcceptTransaction>, rejected_add_ons: Vec<u8>) -> Result<()>{
+        seller_accept_transaction_handler(ctx, rejected_add_ons)
     }
 
     /// COMMISSION SPECIFIC UTILS
     
-    pub fn commit_preview(ctx: Context<CommitPreview>, link: String) -> Result<()>{
-        commit_preview_handler(ctx, link)
+    pub fn commit_preview(ctx: Context<CommitPreview>, link: String, content_hash: [u8; 32], rate_round: u8) -> Result<()>{
+        commit_preview_handler(ctx, link, content_hash, rate_round)
     }
-    pub fn propose_rate(ctx: Context<UpdateRate>, new_rate: u8) -> Result<()>{
-        propose_rate_handler(ctx, new_rate)
+
+    pub fn request_revision(ctx: Context<RequestRevision>, version: u8, request: String) -> Result<()>{
+        request_revision_handler(ctx, version, request)
+    }
+    pub fn propose_rate(ctx: Context<UpdateRate>, new_rate_bps: u16, note: String) -> Result<()>{
+        propose_rate_handler(ctx, new_rate_bps, note)
     }
     pub fn accept_rate(ctx: Context<UpdateRate>) -> Result<()>{
         accept_rate_handler(ctx)
     }
+    pub fn reject_rate(ctx: Context<UpdateRate>) -> Result<()>{
+        reject_rate_handler(ctx)
+    }
+    pub fn withdraw_rate(ctx: Context<UpdateRate>) -> Result<()>{
+        withdraw_rate_handler(ctx)
+    }
+    pub fn migrate_rates(ctx: Context<MigrateRates>) -> Result<()>{
+        migrate_rates_handler(ctx)
+    }
+
+    /// PRICE RENEGOTIATION
+
+    pub fn propose_price(ctx: Context<ProposePrice>, new_price: u64) -> Result<()>{
+        propose_price_handler(ctx, new_price)
+    }
+
+    pub fn accept_price_sol(ctx: Context<AcceptPriceSol>) -> Result<()>{
+        accept_price_sol_handler(ctx)
+    }
+
+    pub fn accept_price_spl(ctx: Context<AcceptPriceSpl>) -> Result<()>{
+        accept_price_spl_handler(ctx)
+    }
+
+    /// MILESTONES
+
+    pub fn set_milestones(ctx: Context<SetMilestones>, amounts: Vec<u64>) -> Result<()>{
+        set_milestones_handler(ctx, amounts)
+    }
+
+    pub fn commit_milestone(ctx: Context<CommitInitData>, index: u8, link: String, key_mask: u64) -> Result<()>{
+        commit_milestone_handler(ctx, index, link, key_mask)
+    }
+
+    pub fn approve_milestone_sol<'a>(ctx: Context<'_, '_, '_, 'a, ApproveMilestoneSol<'a>>, index: u8) -> Result<()>{
+        approve_milestone_sol_handler(ctx, index)
+    }
+
+    pub fn approve_milestone_spl<'a>(ctx: Context<'_, '_, '_, 'a, ApproveMilestoneSpl<'a>>, index: u8) -> Result<()>{
+        approve_milestone_spl_handler(ctx, index)
+    }
+
+    pub fn decline_milestone(ctx: Context<BuyerDeny>, index: u8) -> Result<()>{
+        decline_milestone_handler(ctx, index)
+    }
+
+    /// DISPUTES
+
+    pub fn open_dispute(ctx: Context<DisputeParticipant>) -> Result<()>{
+        open_dispute_handler(ctx)
+    }
+
+    pub fn submit_evidence(ctx: Context<DisputeParticipant>, link: String) -> Result<()>{
+        submit_evidence_handler(ctx, link)
+    }
+
+    pub fn rule_dispute(ctx: Context<RuleDispute>, seller_rate_bps: u16) -> Result<()>{
+        rule_dispute_handler(ctx, seller_rate_bps)
+    }
+
+    pub fn contest_deny(ctx: Context<DisputeParticipant>) -> Result<()>{
+        contest_deny_handler(ctx)
+    }
+
+    /// DEADLINE CRANKS
+
+    pub fn expire_delivery(ctx: Context<ExpireDelivery>) -> Result<()>{
+        expire_delivery_handler(ctx)
+    }
+
+    pub fn expire_confirmation(ctx: Context<ExpireConfirmation>) -> Result<()>{
+        expire_confirmation_handler(ctx)
+    }
+
+    pub fn uphold_deny(ctx: Context<UpholdDeny>) -> Result<()>{
+        uphold_deny_handler(ctx)
+    }
+
+    /// TIPS
+
+    pub fn tip_sol(ctx: Context<TipSol>, amount: u64) -> Result<()>{
+        tip_sol_handler(ctx, amount)
+    }
+
+    pub fn tip_spl(ctx: Context<TipSpl>, amount: u64) -> Result<()>{
+        tip_spl_handler(ctx, amount)
+    }
+
+    //////////////////////////////
+    /// CONFIG
+
+    pub fn init_commission_config(ctx: Context<InitCommissionConfig>, fee_bps: u16, referral_bps: u16, discount_rate: u8, treasury: Pubkey, tip_fee_bps: u16, multisig_wallet: Pubkey) -> Result<()>{
+        init_commission_config_handler(ctx, fee_bps, referral_bps, discount_rate, treasury, tip_fee_bps, multisig_wallet)
+    }
+
+    pub fn update_commission_config(ctx: Context<UpdateCommissionConfig>, fee_bps: u16, referral_bps: u16, discount_rate: u8, treasury: Pubkey, tip_fee_bps: u16, multisig_wallet: Pubkey) -> Result<()>{
+        update_commission_config_handler(ctx, fee_bps, referral_bps, discount_rate, treasury, tip_fee_bps, multisig_wallet)
+    }
 
     //////////////////////////////
     /// PRODUCT
+
+    pub fn init_product_terms(ctx: Context<InitProductTerms>, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>) -> Result<()>{
+        init_product_terms_handler(ctx, free_revisions, base_price, min_price, currency_prices, instant_accept, add_ons)
+    }
+
+    pub fn update_product_terms(ctx: Context<UpdateProductTerms>, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>) -> Result<()>{
+        update_product_terms_handler(ctx, free_revisions, base_price, min_price, currency_prices, instant_accept, add_ons)
+    }
+
+    /// QUOTES
+
+    pub fn create_quote(ctx: Context<CreateQuote>, buyer: u64, price: u64, currency: Pubkey, deadline: i64, delivery_window: i64, scope_hash: [u8; 32]) -> Result<()>{
+        create_quote_handler(ctx, buyer, price, currency, deadline, delivery_window, scope_hash)
+    }
+
+    pub fn withdraw_quote(ctx: Context<WithdrawQuote>) -> Result<()>{
+        withdraw_quote_handler(ctx)
+    }
+
+    pub fn accept_quote_sol(ctx: Context<AcceptQuoteSol>, seller_tx_index: u8, buyer_tx_index: u8) -> Result<()>{
+        accept_quote_sol_handler(ctx, seller_tx_index, buyer_tx_index)
+    }
+
+    pub fn accept_quote_spl(ctx: Context<AcceptQuoteSpl>, seller_tx_index: u8, buyer_tx_index: u8) -> Result<()>{
+        accept_quote_spl_handler(ctx, seller_tx_index, buyer_tx_index)
+    }
     
 
     /// MODIFIERS
@@ -120,5 +287,9 @@ pub mod orbit_commission_market {
     pub fn leave_review(ctx: Context<LeaveReview>, rating: u8) -> Result<()>{
         CommissionTransaction::leave_review(ctx, rating)
     }
+
+    pub fn leave_text_review(ctx: Context<LeaveReview>, rating: u8, review_hash: [u8; 32]) -> Result<()>{
+        leave_text_review_handler(ctx, rating, review_hash)
+    }
 }
 
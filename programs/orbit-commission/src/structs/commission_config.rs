@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct CommissionConfig{
+    pub fee_bps: u16, // 2 ; platform cut of an accepted commission
+    pub referral_bps: u16, // 2 ; paid out of the fee to both the reflink owner and the buyer
+    pub discount_rate: u8, // 1 ; percentage of the price paid when a dispute discount is used
+    pub treasury: Pubkey, // 32
+    pub tip_fee_bps: u16, // 2 ; platform cut of buyer tips, 0 sends the whole tip to the seller
+    pub multisig_wallet: Pubkey, // 32 ; receives the platform cut of tips
+}
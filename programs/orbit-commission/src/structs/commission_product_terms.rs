@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// most currencies a product can be listed in
+pub const MAX_ACCEPTED_CURRENCIES: usize = 4;
+/// most add-ons a product can offer
+pub const MAX_ADD_ONS: usize = 8;
+pub const PRODUCT_TERMS_SPACE: usize = 8 + 354;
+
+/// seller owned terms for one CommissionProduct.
+/// copied onto each transaction when it is opened, so later edits never touch open commissions
+#[account]
+pub struct CommissionProductTerms{
+    pub product: Pubkey, // 32
+    pub free_revisions: u8, // 1 ; preview revisions the buyer gets before new rounds must carry a rate proposal
+    pub base_price: u64, // 8 ; listed price
+    pub min_price: u64, // 8 ; lowest price a buyer can open at, 0 for base_price
+    pub currency_prices: Vec<CurrencyPrice>, // 4 + 40 * MAX_ACCEPTED_CURRENCIES ; empty takes any currency at base_price
+    pub instant_accept: bool, // 1 ; buyers can open already accepted and funded
+    pub add_ons: Vec<AddOn>, // 4 + 17 * MAX_ADD_ONS
+}
+
+/// fixed price of a product in one currency. mint is the system program id for sol
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct CurrencyPrice{
+    pub mint: Pubkey, // 32
+    pub price: u64, // 8
+}
+
+/// optional extra a buyer can pick at open (commercial rights, extra characters, rush delivery).
+/// priced in whatever currency the transaction is opened in, on top of the listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct AddOn{
+    pub id: u8, // 1
+    pub price: u64, // 8
+    pub delivery_delta: i64, // 8 ; seconds added to the delivery window, negative for rush. 0 for none
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// a seller's offer to one buyer for one product. accepting it opens the
+/// transaction already agreed on and funded, the quote account closes back to the seller
+#[account]
+pub struct CommissionQuote{
+    pub seller: u64, // 8
+    pub buyer: u64, // 8
+    pub product: Pubkey, // 32
+    pub price: u64, // 8
+    pub currency: Pubkey, // 32 ; system program id for sol
+    pub deadline: i64, // 8 ; the quote can't be accepted after it
+    pub delivery_window: i64, // 8 ; seconds from acceptance to the delivery deadline
+    pub scope_hash: [u8; 32], // 32 ; hash of the off chain scope document
+}
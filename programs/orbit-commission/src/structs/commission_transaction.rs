@@ -1,25 +1,210 @@
-use anchor_lang::prelude::*;
-use orbit_transaction::transaction_struct::OrbitTransactionStruct;
-
-#[account]
-pub struct CommissionTransaction{
-    pub metadata: OrbitTransactionStruct, // 120
-
-    pub preview_address: String, // 43
-    pub preview_rate: u8, // 1
-    pub last_rate_offerer: u64, // 8
-
-    pub close_rate: u8, // 1
-
-    pub data_address: String, // 43
-    pub num_keys: u64, // 8
-    pub key_arr: Vec<Pubkey>, // up to 2048 ; 64 keys
-    pub final_decision: BuyerDecisionState, // 1
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum BuyerDecisionState{
-    Null,
-    Declined,
-    Accept
-}
\ No newline at end of file
+use anchor_lang::prelude::*;
+use orbit_transaction::transaction_struct::OrbitTransactionStruct;
+
+/// seconds the seller has after funding to reach Shipped
+pub const DELIVERY_WINDOW: i64 = 60 * 60 * 24 * 30;
+/// seconds the buyer has after shipping to make a decision
+pub const CONFIRMATION_WINDOW: i64 = 60 * 60 * 24 * 7;
+/// seconds the seller has to contest a buyer deny before it can be upheld
+pub const CONTEST_WINDOW: i64 = 60 * 60 * 24 * 7;
+/// most milestones a single commission can be split into
+pub const MAX_MILESTONES: usize = 8;
+/// most evidence links that can be posted to one dispute
+pub const MAX_EVIDENCE: usize = 8;
+/// 100% expressed in basis points
+pub const BPS_DENOMINATOR: u16 = 10000;
+/// rate offers kept on the transaction, older ones are overwritten
+pub const MAX_RATE_OFFERS: usize = 8;
+/// proposals allowed before negotiation is closed. proposing past it expires the open offer
+pub const MAX_RATE_ROUNDS: u8 = 16;
+/// longest note that can ride along with a rate offer
+pub const MAX_RATE_NOTE_LEN: usize = 32;
+/// longest preview, data, milestone or evidence link
+pub const MAX_LINK_LEN: usize = 64;
+/// most keys a seller can commit
+pub const MAX_KEYS: usize = 64;
+/// largest sealed key ciphertext, room for an x25519 sealed box around a 32 byte key plus nonce
+pub const MAX_SEALED_KEY_LEN: usize = 128;
+/// most preview rounds a seller can post
+pub const MAX_PREVIEW_ROUNDS: usize = 8;
+/// seconds after closing that both sides can still leave a review
+pub const REVIEW_WINDOW: i64 = 60 * 60 * 24 * 30;
+
+/// accounts are opened at this size and grown as the seller and buyer fill them in.
+/// see fit_transaction
+pub const BASE_TRANSACTION_SPACE: usize = 8 + 393;
+
+#[account]
+pub struct CommissionTransaction{
+    pub metadata: OrbitTransactionStruct, // 120
+
+    pub preview_address: String, // 4 + MAX_LINK_LEN
+    pub preview_rate: u8, // 1 ; legacy whole percentage, see preview_rate_bps
+    pub last_rate_offerer: u64, // 8
+
+    pub close_rate: u8, // 1 ; legacy whole percentage, see close_rate_bps
+
+    pub data_address: String, // 4 + MAX_LINK_LEN
+    pub num_keys: u64, // 8
+    pub key_arr: Vec<Pubkey>, // 4 + 32 * MAX_KEYS
+    pub final_decision: BuyerDecisionState, // 1
+
+    pub delivery_deadline: i64, // 8
+    pub confirmation_deadline: i64, // 8
+
+    pub milestones: Vec<Milestone>, // up to 684 ; 8 milestones
+    pub milestones_released: u64, // 8
+
+    pub dispute_state: DisputeState, // 1
+    pub dispute_evidence: Vec<DisputeEvidence>, // up to 612 ; 8 links
+
+    pub rates_in_bps: bool, // 1 ; false for transactions opened under the percentage format
+    pub rate_bps: u16, // 2 ; seller share of the price once fees are taken
+    pub preview_rate_bps: u16, // 2
+    pub close_rate_bps: u16, // 2
+
+    pub rate_rounds: u8, // 1
+    pub rate_offers: Vec<RateOffer>, // up to 444 ; 8 offers, ring buffer indexed by rate_rounds
+
+    pub proposed_price: u64, // 8 ; what the escrow would hold, discount applied. 0 when nothing is on the table
+    pub price_proposer: u64, // 8
+
+    pub keys_committed: bool, // 1 ; key_arr and content_hashes are write once
+    pub content_hashes: Vec<[u8; 32]>, // 4 + 32 * MAX_KEYS ; hash of each deliverable, same order as key_arr
+
+    pub buyer_encryption_key: [u8; 32], // 32 ; x25519 public key, all zero until the buyer registers one
+    pub sealed_keys: Vec<SealedKey>, // 4 + (5 + MAX_SEALED_KEY_LEN) * MAX_KEYS
+
+    pub free_revisions: u8, // 1 ; from CommissionProductTerms at open
+    pub preview_rounds: Vec<PreviewRound>, // up to 1428 ; 8 rounds, preview_address mirrors the newest link
+
+    pub scope_hash: [u8; 32], // 32 ; from the seller quote, zero for buyer opened transactions
+
+    pub cancel_offer: Option<CancelOffer>, // 1 + 10
+
+    pub outcome: CommissionOutcome, // 1 ; how the commission ended, decides who can be reviewed
+    pub review_deadline: i64, // 8 ; 0 for transactions closed before outcomes were recorded
+    pub buyer_review_hash: [u8; 32], // 32 ; hash of the buyer's written review, zero if none
+    pub seller_review_hash: [u8; 32], // 32
+
+    pub tipped: u64, // 8 ; everything the buyer tipped after their decision, in the transaction currency
+
+    pub add_ons: Vec<OrderedAddOn>, // 4 + 18 * MAX_ADD_ONS ; picked at open, rejected ones are no longer in the price
+
+    pub used_discount: bool, // 1 ; open took one of the buyer's dispute discounts, refunded if the buyer gets their money back
+
+    pub contest_deadline: i64, // 8 ; end of the seller's window to contest a deny, 0 when nothing was denied
+
+    pub seller_rent: u64, // 8 ; lamports the seller put into this account as it grew, the buyer paid the rest
+}
+
+/// an add-on as the buyer ordered it, copied from CommissionProductTerms at open
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct OrderedAddOn{
+    pub id: u8, // 1
+    pub price: u64, // 8 ; as escrowed, after any dispute discount
+    pub delivery_delta: i64, // 8
+    pub state: AddOnState, // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum AddOnState{
+    Pending,
+    Accepted,
+    Rejected
+}
+
+/// one side's proposal to end a funded commission early
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct CancelOffer{
+    pub proposer: u64, // 8
+    pub seller_bps: u16, // 2 ; seller's cut of what is still escrowed, before fees. the buyer gets the rest
+}
+
+/// a deliverable key sealed to buyer_encryption_key, only the buyer can open it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct SealedKey{
+    pub index: u8, // 1
+    pub ciphertext: Vec<u8>, // 4 + MAX_SEALED_KEY_LEN
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PreviewRound{
+    pub version: u8, // 1 ; starts at 1
+    pub link: String, // 4 + MAX_LINK_LEN
+    pub content_hash: [u8; 32], // 32
+    pub timestamp: i64, // 8
+    pub rate_round: u8, // 1 ; rate offer (1 based round) posted alongside, 0 for none
+    pub revision_request: String, // 4 + MAX_LINK_LEN ; buyer annotations, empty until asked
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct RateOffer{
+    pub rate_bps: u16, // 2
+    pub proposer: u64, // 8
+    pub timestamp: i64, // 8
+    pub note: String, // 36
+    pub status: RateOfferStatus, // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum RateOfferStatus{
+    Pending,
+    Countered,
+    Accepted,
+    Rejected,
+    Withdrawn,
+    /// countered after the last round, nobody can answer it anymore
+    Expired,
+    /// accepted and already paid for a preview round
+    Consumed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct Milestone{
+    pub amount: u64, // 8
+    pub preview_address: String, // 4 + MAX_LINK_LEN
+    pub key_mask: u64, // 8 ; bits into key_arr
+    pub state: MilestoneState, // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct DisputeEvidence{
+    pub submitter: u64, // 8
+    pub link: String, // 4 + MAX_LINK_LEN
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum DisputeState{
+    None,
+    Open,
+    Ruled,
+    /// the buyer denied, the seller can contest until contest_deadline
+    Denied
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum MilestoneState{
+    Pending,
+    Submitted,
+    Approved,
+    /// the seller can resubmit it or open a dispute over it
+    Declined
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum CommissionOutcome{
+    Pending,
+    Accepted,
+    Denied,
+    Ruled,
+    EarlyDeclined,
+    Cancelled
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum BuyerDecisionState{
+    Null,
+    Declined,
+    Accept
+}
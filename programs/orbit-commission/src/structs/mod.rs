@@ -1,2 +1,9 @@
-pub mod commission_transaction;
-pub use commission_transaction::*;
\ No newline at end of file
+pub mod commission_transaction;
+pub mod commission_config;
+pub mod commission_product_terms;
+pub mod commission_quote;
+
+pub use commission_transaction::*;
+pub use commission_config::*;
+pub use commission_product_terms::*;
+pub use commission_quote::*;
@@ -0,0 +1,285 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke_signed,
+        program_pack::Pack,
+        system_instruction::create_account
+    }
+};
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{
+            TransferFeeAmount,
+            TransferFeeConfig
+        },
+        ExtensionType,
+        StateWithExtensions
+    },
+    state::{
+        Account as TokenState,
+        Mint as MintState
+    }
+};
+use crate::{
+    CommissionMarketErrors,
+    BPS_DENOMINATOR,
+    MAX_MILESTONES
+};
+
+//////////////////////////////////////////////////////////////////////////
+/// TOKEN INTERFACE
+/// spl escrows can live under the legacy token program or token-2022.
+/// both share the base account and mint layouts, so everything here reads
+/// through the token-2022 unpackers and cpis with whichever program owns the mint
+
+pub fn is_token_program(key: &Pubkey) -> bool{
+    (*key == anchor_spl::token::ID) || (*key == spl_token_2022::ID)
+}
+
+pub fn is_mint(info: &AccountInfo, token_program: &Pubkey) -> bool{
+    if info.owner != token_program || !is_token_program(info.owner){
+        return false
+    }
+    let data = info.try_borrow_data();
+    match data{
+        Ok(data) => StateWithExtensions::<MintState>::unpack(&data).is_ok(),
+        Err(_) => false
+    }
+}
+
+/// token account of `mint` held by `owner`, under either token program
+pub fn is_token_account(info: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> bool{
+    if !is_token_program(info.owner){
+        return false
+    }
+    let data = info.try_borrow_data();
+    match data{
+        Ok(data) => match StateWithExtensions::<TokenState>::unpack(&data){
+            Ok(state) => (state.base.mint == *mint) && (state.base.owner == *owner),
+            Err(_) => false
+        },
+        Err(_) => false
+    }
+}
+
+pub fn amount(info: &AccountInfo) -> Result<u64>{
+    let data = info.try_borrow_data()?;
+    match StateWithExtensions::<TokenState>::unpack(&data){
+        Ok(state) => Ok(state.base.amount),
+        Err(_) => err!(CommissionMarketErrors::InvalidTokenAccount)
+    }
+}
+
+pub fn decimals(mint: &AccountInfo) -> Result<u8>{
+    let data = mint.try_borrow_data()?;
+    match StateWithExtensions::<MintState>::unpack(&data){
+        Ok(state) => Ok(state.base.decimals),
+        Err(_) => err!(CommissionMarketErrors::InvalidTokenAccount)
+    }
+}
+
+/// transfer fees held back in a token account. it can't be closed until they are harvested
+pub fn withheld_fees(info: &AccountInfo) -> Result<u64>{
+    let data = info.try_borrow_data()?;
+    let state = match StateWithExtensions::<TokenState>::unpack(&data){
+        Ok(state) => state,
+        Err(_) => return err!(CommissionMarketErrors::InvalidTokenAccount)
+    };
+    match state.get_extension::<TransferFeeAmount>(){
+        Ok(amount) => Ok(amount.withheld_amount.into()),
+        Err(_) => Ok(0)
+    }
+}
+
+/// transfers out of one escrow: seller, platform, referrer and buyer on close,
+/// and the same again for every milestone released before it
+const PAYOUT_TRANSFERS: u64 = 4 * (MAX_MILESTONES as u64 + 1);
+
+/// the mint's transfer fee this epoch as (basis points, maximum fee). None without the extension
+fn epoch_fee(mint: &AccountInfo) -> Result<Option<(u16, u64)>>{
+    let data = mint.try_borrow_data()?;
+    let state = match StateWithExtensions::<MintState>::unpack(&data){
+        Ok(state) => state,
+        Err(_) => return err!(CommissionMarketErrors::InvalidTokenAccount)
+    };
+    match state.get_extension::<TransferFeeConfig>(){
+        Ok(config) => {
+            let fee = config.get_epoch_fee(Clock::get()?.epoch);
+            Ok(Some((u16::from(fee.transfer_fee_basis_points), u64::from(fee.maximum_fee))))
+        },
+        Err(_) => Ok(None)
+    }
+}
+
+/// fee withheld by the mint when `amount` is sent this epoch. 0 for mints without the extension
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64>{
+    let data = mint.try_borrow_data()?;
+    let state = match StateWithExtensions::<MintState>::unpack(&data){
+        Ok(state) => state,
+        Err(_) => return err!(CommissionMarketErrors::InvalidTokenAccount)
+    };
+    match state.get_extension::<TransferFeeConfig>(){
+        Ok(config) => match config.calculate_epoch_fee(Clock::get()?.epoch, amount){
+            Some(fee) => Ok(fee),
+            None => err!(CommissionMarketErrors::InvalidTokenAccount)
+        },
+        Err(_) => Ok(0)
+    }
+}
+
+/// what the sender has to move so the receiver is credited `net` after the transfer fee.
+/// the fee rounds up, so ceil(net / (1 - rate)) always nets enough, and the fee cap
+/// bounds it at net + maximum fee
+pub fn gross_up(mint: &AccountInfo, net: u64) -> Result<u64>{
+    let (fee_bps, maximum_fee) = match epoch_fee(mint)?{
+        Some(fee) => fee,
+        None => return Ok(net)
+    };
+    let capped = net.checked_add(maximum_fee);
+    let gross = if fee_bps >= BPS_DENOMINATOR{
+        capped
+    }else{
+        let kept = (BPS_DENOMINATOR - fee_bps) as u128;
+        let inverse = u64::try_from((net as u128 * BPS_DENOMINATOR as u128 + kept - 1) / kept).ok();
+        match (inverse, capped){
+            (Some(inverse), Some(capped)) => Some(inverse.min(capped)),
+            (inverse, capped) => inverse.or(capped)
+        }
+    };
+    match gross{
+        Some(gross) if gross - transfer_fee(mint, gross)? >= net => Ok(gross),
+        _ => err!(CommissionMarketErrors::InvalidTokenAccount)
+    }
+}
+
+/// what an escrow holding `amount` keeps on top of it for the transfer fees on the way out.
+/// a payout grossed up from net n costs at most ceil(n * rate / (1 - rate)), so summed over
+/// the payouts that is the same bound on the whole amount plus a unit of rounding per transfer.
+/// no transfer pays more than the cap either way
+pub fn escrow_reserve(mint: &AccountInfo, amount: u64) -> Result<u64>{
+    let (fee_bps, maximum_fee) = match epoch_fee(mint)?{
+        Some(fee) => fee,
+        None => return Ok(0)
+    };
+    let capped = maximum_fee.saturating_mul(PAYOUT_TRANSFERS);
+    if fee_bps >= BPS_DENOMINATOR{
+        return Ok(capped)
+    }
+    let kept = (BPS_DENOMINATOR - fee_bps) as u128;
+    let fees = (amount as u128 * fee_bps as u128 + kept - 1) / kept + PAYOUT_TRANSFERS as u128;
+    Ok(u64::try_from(fees).unwrap_or(u64::MAX).min(capped))
+}
+
+/// funds `escrow` with `amount` plus its escrow_reserve, so recipients get their full share.
+/// the surplus goes back to the buyer on close
+pub fn fund_escrow<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    escrow: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    amount: u64
+) -> Result<()>{
+    let total = amount.checked_add(escrow_reserve(&mint, amount)?).ok_or(CommissionMarketErrors::InvalidTokenAccount)?;
+    let gross = gross_up(&mint, total)?;
+    transfer_checked(token_program, from, mint, escrow, authority, &[], gross)
+}
+
+/// transfer_checked against whichever token program owns the mint. zero amounts are skipped
+pub fn transfer_checked<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64
+) -> Result<()>{
+    if amount == 0{
+        return Ok(())
+    }
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals(&mint)?
+    )?;
+    invoke_signed(
+        &ix,
+        &[from, mint, to, authority, token_program],
+        signer_seeds
+    ).map_err(Into::into)
+}
+
+/// allocates and initializes the escrow token account at its pda.
+/// token-2022 mints may require extra account extensions (transfer fee amount etc.)
+pub fn create_escrow<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    authority: &Pubkey,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    escrow_seeds: &[&[&[u8]]]
+) -> Result<()>{
+    let space = if *token_program.key == spl_token_2022::ID{
+        let data = mint.try_borrow_data()?;
+        let mint_state = match StateWithExtensions::<MintState>::unpack(&data){
+            Ok(state) => state,
+            Err(_) => return err!(CommissionMarketErrors::InvalidTokenAccount)
+        };
+        let mint_extensions = mint_state.get_extension_types()?;
+        let required = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+        ExtensionType::get_account_len::<TokenState>(&required)
+    }else{
+        TokenState::LEN
+    };
+
+    invoke_signed(
+        &create_account(
+            payer.key,
+            escrow.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            token_program.key
+        ),
+        &[payer, escrow.clone(), system_program],
+        escrow_seeds
+    )?;
+
+    invoke_signed(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program.key,
+            escrow.key,
+            mint.key,
+            authority
+        )?,
+        &[escrow, mint, token_program],
+        &[]
+    ).map_err(Into::into)
+}
+
+/// closes an emptied escrow token account, the rent goes to `destination`
+pub fn close_escrow<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()>{
+    invoke_signed(
+        &spl_token_2022::instruction::close_account(
+            token_program.key,
+            escrow.key,
+            destination.key,
+            authority.key,
+            &[]
+        )?,
+        &[escrow, destination, authority, token_program],
+        signer_seeds
+    ).map_err(Into::into)
+}
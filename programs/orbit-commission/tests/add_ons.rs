@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+use orbit_commission::{AddOn, AddOnState, DELIVERY_WINDOW};
+use orbit_transaction::transaction_struct::TransactionState;
+
+const RIGHTS: AddOn = AddOn{id: 1, price: PRICE / 2, delivery_delta: 0};
+const RUSH: AddOn = AddOn{id: 2, price: PRICE / 4, delivery_delta: -60 * 60 * 24 * 7};
+
+async fn listed() -> Fixture{
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, PRICE, 0, vec![], false, vec![RIGHTS, RUSH]).await;
+    f
+}
+
+#[tokio::test]
+async fn add_ons_are_priced_into_the_escrow(){
+    let mut f = listed().await;
+    f.open_sol_with_add_ons(PRICE, false, vec![1, 2]).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.metadata.transaction_price, PRICE + RIGHTS.price + RUSH.price);
+    assert!(tx.add_ons.iter().all(|add_on| add_on.state == AddOnState::Pending));
+
+    f.seller_accept().await;
+    let before = f.now().await;
+    f.fund_sol().await;
+    assert_eq!(f.balance(f.escrow).await, PRICE + RIGHTS.price + RUSH.price);
+
+    let funded_at = f.transaction().await.delivery_deadline - DELIVERY_WINDOW - RUSH.delivery_delta;
+    assert!((before..=f.now().await).contains(&funded_at));
+}
+
+#[tokio::test]
+async fn seller_rejected_add_ons_come_off_the_price(){
+    let mut f = listed().await;
+    f.open_sol_with_add_ons(PRICE, false, vec![1, 2]).await.unwrap();
+    assert!(f.try_seller_accept(vec![3]).await.is_err());
+    f.try_seller_accept(vec![2]).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.metadata.transaction_price, PRICE + RIGHTS.price);
+    assert!(tx.add_ons[0].state == AddOnState::Accepted);
+    assert!(tx.add_ons[1].state == AddOnState::Rejected);
+
+    let before = f.now().await;
+    f.fund_sol().await;
+    let funded_at = f.transaction().await.delivery_deadline - DELIVERY_WINDOW;
+    assert!((before..=f.now().await).contains(&funded_at));
+}
+
+#[tokio::test]
+async fn add_ons_are_priced_off_the_listing(){
+    let mut f = listed().await;
+    f.open_sol_with_add_ons(PRICE * 2, false, vec![1]).await.unwrap();
+    assert_eq!(f.transaction().await.metadata.transaction_price, PRICE + RIGHTS.price);
+}
+
+#[tokio::test]
+async fn instant_accept_takes_every_add_on(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, PRICE, 0, vec![], true, vec![RIGHTS, RUSH]).await;
+    let before = f.now().await;
+    f.open_instant_sol(PRICE, false, vec![1, 2]).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerFunded);
+    assert!(tx.add_ons.iter().all(|add_on| add_on.state == AddOnState::Accepted));
+    assert_eq!(f.balance(f.escrow).await, PRICE + RIGHTS.price + RUSH.price);
+
+    let funded_at = tx.delivery_deadline - DELIVERY_WINDOW - RUSH.delivery_delta;
+    assert!((before..=f.now().await).contains(&funded_at));
+}
+
+#[tokio::test]
+async fn only_listed_add_ons_can_be_ordered(){
+    let mut f = listed().await;
+    assert!(f.open_sol_with_add_ons(PRICE, false, vec![3]).await.is_err());
+    assert!(f.open_sol_with_add_ons(PRICE, false, vec![1, 1]).await.is_err());
+    f.open_sol_with_add_ons(PRICE, false, vec![1]).await.unwrap();
+}
+
+#[tokio::test]
+async fn discounts_cover_add_ons(){
+    let mut f = Fixture::new(Options{dispute_discounts: 1, ..Options::default()}).await;
+    f.init_product_terms_with(0, PRICE, 0, vec![], false, vec![RIGHTS]).await;
+    f.open_sol_with_add_ons(PRICE, true, vec![1]).await.unwrap();
+
+    let discounted = |price: u64| price * DISCOUNT_RATE as u64 / 100;
+    let tx = f.transaction().await;
+    assert_eq!(tx.metadata.transaction_price, discounted(PRICE + RIGHTS.price));
+    assert_eq!(tx.add_ons[0].price, discounted(RIGHTS.price));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn buyer_cancels_before_accept_and_gets_rent_back(){
+    let mut f = Fixture::new(Options::default()).await;
+    let before = f.balance(f.buyer.pubkey()).await;
+    f.open_sol(PRICE, false).await;
+    assert!(f.balance(f.buyer.pubkey()).await < before);
+
+    f.buyer_cancel_sol().await.unwrap();
+    assert!(f.ctx.banks_client.get_account(f.commission_tx).await.unwrap().is_none());
+    assert_eq!(f.balance(f.buyer.pubkey()).await, before);
+}
+
+#[tokio::test]
+async fn buyer_cancels_after_seller_accept(){
+    let mut f = Fixture::new(Options{dispute_discounts: 1, ..Options::default()}).await;
+    f.open_sol(PRICE, true).await;
+    f.seller_accept().await;
+    // planning milestones grows the account on the seller's dime
+    let price = f.transaction().await.metadata.transaction_price;
+    f.set_milestones(vec![price / 2, price - price / 2]).await.unwrap();
+    let seller_rent = f.transaction().await.seller_rent;
+    assert!(seller_rent > 0);
+
+    let seller = f.balance(f.seller.pubkey()).await;
+    f.buyer_cancel_sol().await.unwrap();
+    assert!(f.ctx.banks_client.get_account(f.commission_tx).await.unwrap().is_none());
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, seller_rent);
+}
+
+#[tokio::test]
+async fn only_discounted_opens_are_marked(){
+    let mut f = Fixture::new(Options{dispute_discounts: 1, ..Options::default()}).await;
+    f.open_sol(PRICE, true).await;
+    assert!(f.transaction().await.used_discount);
+
+    // asking for a discount without one left opens at the normal rate
+    let mut f = Fixture::new(Options::default()).await;
+    f.open_sol(PRICE, true).await;
+    let tx = f.transaction().await;
+    assert!(!tx.used_discount);
+    assert_eq!(tx.metadata.transaction_price, PRICE);
+}
+
+#[tokio::test]
+async fn funded_transactions_cant_be_cancelled(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_sol().await;
+    assert!(f.buyer_cancel_sol().await.is_err());
+}
+
+#[tokio::test]
+async fn spl_cancel_closes_the_escrow(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(PRICE).await;
+    let before = f.balance(f.buyer.pubkey()).await;
+    f.open_spl(PRICE, false).await;
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_some());
+
+    f.buyer_cancel_spl().await.unwrap();
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_none());
+    assert!(f.ctx.banks_client.get_account(f.commission_tx).await.unwrap().is_none());
+    assert_eq!(f.balance(f.buyer.pubkey()).await, before);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, PRICE);
+}
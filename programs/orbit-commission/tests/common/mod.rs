@@ -0,0 +1,1513 @@
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult,
+        instruction::Instruction,
+        system_instruction,
+        system_program
+    },
+    AccountDeserialize,
+    AccountSerialize,
+    Discriminator,
+    InstructionData,
+    ToAccountMetas
+};
+use anchor_spl::token::spl_token;
+use spl_token_2022::extension::{
+    transfer_fee::instruction as transfer_fee_ix,
+    ExtensionType,
+    StateWithExtensions
+};
+use market_accounts::OrbitMarketAccount;
+use orbit_commission::{
+    AddOn,
+    CommissionConfig,
+    CommissionTransaction,
+    CommissionTransitionEvent,
+    CurrencyPrice,
+    CONTEST_WINDOW
+};
+use orbit_product::CommissionProduct;
+use orbit_transaction::{
+    BuyerOpenTransactions,
+    SellerOpenTransactions,
+    TransactionType
+};
+use solana_program_test::{
+    processor,
+    BanksClientError,
+    ProgramTest,
+    ProgramTestContext
+};
+use solana_sdk::{
+    account::{Account as SolanaAccount, AccountSharedData},
+    signature::{Keypair, Signature, Signer as _},
+    transaction::Transaction
+};
+
+pub const LAMPORTS: u64 = 1_000_000_000;
+pub const PRICE: u64 = 100_000_000;
+pub const FEE_BPS: u16 = 500;
+pub const REFERRAL_BPS: u16 = 100;
+pub const DISCOUNT_RATE: u8 = 95;
+pub const TIP_FEE_BPS: u16 = 1000;
+
+pub const BUYER_ID: u64 = 1;
+pub const SELLER_ID: u64 = 2;
+pub const PRODUCT_INDEX: u64 = 7;
+pub const SELLER_TX_INDEX: u8 = 0;
+pub const BUYER_TX_INDEX: u8 = 0;
+
+//////////////////////////////////////////////////////////////////////////
+/// PROCESSORS
+
+/// anchor entrypoints want the account slice to live as long as the infos.
+/// leaking is fine inside a test process
+fn commission_processor(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult{
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    orbit_commission::entry(program_id, accounts, data)
+}
+
+/// product, transaction and accounts are exercised through their own suites.
+/// here they only have to accept the cpi so the commission side can be checked
+fn stub_processor(_program_id: &Pubkey, _accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult{
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// ACCOUNT BUILDERS
+
+/// starts from an all zero borsh body, so only the fields a test cares about
+/// have to be filled in. the discriminator is written by try_serialize
+pub fn anchor_account<T, F>(space: usize, edit: F) -> SolanaAccount
+where
+    T: AccountSerialize + AccountDeserialize + Owner,
+    F: FnOnce(&mut T)
+{
+    let zeroed = vec![0u8; space.max(10240)];
+    let mut acc = T::try_deserialize_unchecked(&mut &zeroed[..]).unwrap();
+    edit(&mut acc);
+
+    let mut data = Vec::with_capacity(space);
+    acc.try_serialize(&mut data).unwrap();
+    assert!(data.len() <= space, "account does not fit in {} bytes", space);
+    data.resize(space, 0);
+
+    SolanaAccount{
+        lamports: Rent::default().minimum_balance(space),
+        data,
+        owner: T::owner(),
+        executable: false,
+        rent_epoch: 0
+    }
+}
+
+pub fn wallet_account(lamports: u64) -> SolanaAccount{
+    SolanaAccount{
+        lamports,
+        data: vec![],
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// FIXTURE
+
+pub struct Options{
+    pub dispute_discounts: u8,
+    pub reflink: bool,
+    pub encryption_key: [u8; 32],
+}
+
+impl Default for Options{
+    fn default() -> Self{
+        Options{
+            dispute_discounts: 0,
+            reflink: false,
+            encryption_key: [0; 32]
+        }
+    }
+}
+
+pub struct Fixture{
+    pub ctx: ProgramTestContext,
+    pub buyer: Keypair,
+    pub seller: Keypair,
+    pub treasury: Keypair,
+    pub multisig: Keypair,
+    pub reflink_owner: Keypair,
+
+    pub buyer_account: Pubkey,
+    pub seller_account: Pubkey,
+    pub buyer_log: Pubkey,
+    pub seller_log: Pubkey,
+    pub product: Pubkey,
+    pub product_terms: Pubkey,
+    pub reflink: Pubkey,
+
+    pub commission_tx: Pubkey,
+    pub escrow: Pubkey,
+    pub commission_auth: Pubkey,
+    pub commission_config: Pubkey,
+
+    pub mint: Keypair,
+    pub buyer_ata: Keypair,
+    pub seller_ata: Keypair,
+    pub treasury_ata: Keypair,
+    pub multisig_ata: Keypair,
+    pub token_program: Pubkey,
+
+    /// sent with every open, all zero opens without one
+    pub encryption_key: [u8; 32],
+    /// program logs of the last transaction sent
+    pub logs: Vec<String>,
+    sent: HashSet<Signature>,
+}
+
+pub fn log_address(prefix: &[u8], voter_id: u64) -> Pubkey{
+    Pubkey::find_program_address(
+        &[
+            prefix,
+            TransactionType::Commissions.try_to_vec().unwrap().as_slice(),
+            &voter_id.to_le_bytes()
+        ],
+        &orbit_transaction::id()
+    ).0
+}
+
+pub fn market_account_address(wallet: &Pubkey) -> Pubkey{
+    Pubkey::find_program_address(&[b"orbit_account", wallet.as_ref()], &market_accounts::id()).0
+}
+
+impl Fixture{
+    pub async fn new(opts: Options) -> Fixture{
+        let mut pt = ProgramTest::new("orbit_commission", orbit_commission::id(), processor!(commission_processor));
+        pt.prefer_bpf(false);
+        pt.add_program("orbit_product", orbit_product::id(), processor!(stub_processor));
+        pt.add_program("orbit_transaction", orbit_transaction::id(), processor!(stub_processor));
+        pt.add_program("market_accounts", market_accounts::id(), processor!(stub_processor));
+
+        let buyer = Keypair::new();
+        let seller = Keypair::new();
+        let treasury = Keypair::new();
+        let multisig = Keypair::new();
+        let reflink_owner = Keypair::new();
+        for wallet in [&buyer, &seller, &treasury, &multisig, &reflink_owner]{
+            pt.add_account(wallet.pubkey(), wallet_account(LAMPORTS));
+        }
+
+        let buyer_account = market_account_address(&buyer.pubkey());
+        let seller_account = market_account_address(&seller.pubkey());
+        let product = Pubkey::new_unique();
+        let reflink = if opts.reflink {Pubkey::new_unique()} else {Pubkey::default()};
+
+        let buyer_wallet = buyer.pubkey();
+        let seller_wallet = seller.pubkey();
+        pt.add_account(buyer_account, anchor_account::<OrbitMarketAccount, _>(1024, |acc|{
+            acc.voter_id = BUYER_ID;
+            acc.wallet = buyer_wallet;
+            acc.dispute_discounts = opts.dispute_discounts.into();
+            acc.used_reflink = reflink;
+        }));
+        pt.add_account(seller_account, anchor_account::<OrbitMarketAccount, _>(1024, |acc|{
+            acc.voter_id = SELLER_ID;
+            acc.wallet = seller_wallet;
+        }));
+        let reflink_wallet = reflink_owner.pubkey();
+        if opts.reflink{
+            pt.add_account(reflink, anchor_account::<market_accounts::OrbitReflink, _>(1024, |r|{
+                r.reflink_owner = reflink_wallet;
+            }));
+        }
+        pt.add_account(product, anchor_account::<CommissionProduct, _>(1024, |p|{
+            p.metadata.index = PRODUCT_INDEX.try_into().unwrap();
+            p.metadata.owner_catalog = SELLER_ID;
+        }));
+
+        let buyer_log = log_address(b"buyer_transactions", BUYER_ID);
+        let seller_log = log_address(b"seller_transactions", SELLER_ID);
+        pt.add_account(buyer_log, anchor_account::<BuyerOpenTransactions, _>(2048, |_|{}));
+        pt.add_account(seller_log, anchor_account::<SellerOpenTransactions, _>(2048, |_|{}));
+
+        let (commission_config, _) = Pubkey::find_program_address(&[b"commission_config"], &orbit_commission::id());
+        let treasury_wallet = treasury.pubkey();
+        let multisig_wallet = multisig.pubkey();
+        pt.add_account(commission_config, anchor_account::<CommissionConfig, _>(100, |c|{
+            c.fee_bps = FEE_BPS;
+            c.referral_bps = REFERRAL_BPS;
+            c.discount_rate = DISCOUNT_RATE;
+            c.treasury = treasury_wallet;
+            c.tip_fee_bps = TIP_FEE_BPS;
+            c.multisig_wallet = multisig_wallet;
+        }));
+
+        let (commission_tx, _) = Pubkey::find_program_address(
+            &[b"orbit_commission_transaction", seller_log.as_ref(), &[SELLER_TX_INDEX]],
+            &orbit_commission::id()
+        );
+        let (escrow, _) = Pubkey::find_program_address(
+            &[b"orbit_escrow_account", commission_tx.as_ref(), buyer_log.as_ref()],
+            &orbit_commission::id()
+        );
+        let (commission_auth, _) = Pubkey::find_program_address(&[b"market_authority"], &orbit_commission::id());
+        let (product_terms, _) = Pubkey::find_program_address(
+            &[b"commission_product_terms", product.as_ref()],
+            &orbit_commission::id()
+        );
+
+        Fixture{
+            ctx: pt.start_with_context().await,
+            buyer,
+            seller,
+            treasury,
+            multisig,
+            reflink_owner,
+            buyer_account,
+            seller_account,
+            buyer_log,
+            seller_log,
+            product,
+            product_terms,
+            reflink,
+            commission_tx,
+            escrow,
+            commission_auth,
+            commission_config,
+            mint: Keypair::new(),
+            buyer_ata: Keypair::new(),
+            seller_ata: Keypair::new(),
+            treasury_ata: Keypair::new(),
+            multisig_ata: Keypair::new(),
+            token_program: spl_token::ID,
+            encryption_key: opts.encryption_key,
+            logs: Vec::new(),
+            sent: HashSet::new(),
+        }
+    }
+
+    /// opened at PRICE in SOL and accepted by the seller, not funded yet
+    pub async fn accepted() -> Fixture{
+        let mut f = Fixture::new(Options::default()).await;
+        f.open_sol(PRICE, false).await;
+        f.seller_accept().await;
+        f
+    }
+
+    /// opened at PRICE, accepted by the seller and funded in SOL
+    pub async fn funded_sol() -> Fixture{
+        Self::funded_sol_with(Options::default(), false).await
+    }
+
+    pub async fn funded_sol_with(opts: Options, use_discount: bool) -> Fixture{
+        let mut f = Fixture::new(opts).await;
+        f.open_sol(PRICE, use_discount).await;
+        f.seller_accept().await;
+        f.fund_sol().await;
+        f
+    }
+
+    /// same as funded_sol over a fresh mint, the buyer holding `supply` of it
+    pub async fn funded_spl_with(opts: Options, use_discount: bool, supply: u64) -> Fixture{
+        let mut f = Fixture::new(opts).await;
+        f.setup_tokens(supply).await;
+        f.open_spl(PRICE, use_discount).await;
+        f.seller_accept().await;
+        f.fund_spl().await;
+        f
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// CLIENT HELPERS
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError>{
+        let payer = Keypair::from_bytes(&self.ctx.payer.to_bytes()).unwrap();
+        let mut all = vec![&payer];
+        all.extend_from_slice(signers);
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await.unwrap();
+        let mut tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all, blockhash);
+        // retrying a rejected instruction would otherwise be refused as already processed
+        if !self.sent.insert(tx.signatures[0]){
+            let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+            tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all, blockhash);
+            self.sent.insert(tx.signatures[0]);
+        }
+        let processed = self.ctx.banks_client.process_transaction_with_metadata(tx).await?;
+        self.logs = processed.metadata.map(|meta| meta.log_messages).unwrap_or_default();
+        processed.result.map_err(BanksClientError::TransactionError)
+    }
+
+    /// transition events the last transaction emitted, in order
+    pub fn transitions(&self) -> Vec<CommissionTransitionEvent>{
+        self.logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| base64::decode(data).ok())
+            .filter(|data| data.starts_with(&CommissionTransitionEvent::discriminator()))
+            .map(|data| CommissionTransitionEvent::try_from_slice(&data[8..]).unwrap())
+            .collect()
+    }
+
+    pub fn transition(&self) -> CommissionTransitionEvent{
+        let mut events = self.transitions();
+        assert_eq!(events.len(), 1, "expected one transition, logs: {:?}", self.logs);
+        events.remove(0)
+    }
+
+    pub async fn balance(&mut self, key: Pubkey) -> u64{
+        self.ctx.banks_client.get_balance(key).await.unwrap()
+    }
+
+    pub async fn token_balance(&mut self, key: Pubkey) -> u64{
+        let acc = self.ctx.banks_client.get_account(key).await.unwrap().unwrap();
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&acc.data).unwrap().base.amount
+    }
+
+    pub async fn transaction(&mut self) -> CommissionTransaction{
+        let acc = self.ctx.banks_client.get_account(self.commission_tx).await.unwrap().unwrap();
+        CommissionTransaction::try_deserialize(&mut &acc.data[..]).unwrap()
+    }
+
+    /// rewrites the transaction account in place, for states the program can't produce anymore
+    pub async fn edit_transaction<F: FnOnce(&mut CommissionTransaction)>(&mut self, edit: F){
+        let mut acc = self.ctx.banks_client.get_account(self.commission_tx).await.unwrap().unwrap();
+        let mut tx = CommissionTransaction::try_deserialize(&mut &acc.data[..]).unwrap();
+        edit(&mut tx);
+
+        let mut data = Vec::with_capacity(acc.data.len());
+        tx.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= acc.data.len(), "edited transaction outgrew its account");
+        data.resize(acc.data.len(), 0);
+        acc.data = data;
+        self.ctx.set_account(&self.commission_tx, &AccountSharedData::from(acc));
+    }
+
+    fn ix<A: ToAccountMetas, D: InstructionData>(accounts: A, data: D, remaining: &[AccountMeta]) -> Instruction{
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend_from_slice(remaining);
+        Instruction{
+            program_id: orbit_commission::id(),
+            accounts: metas,
+            data: data.data()
+        }
+    }
+
+    /// payouts that split a fee with a reflink take the reflink and its owner wallet
+    pub fn reflink_metas(&self) -> Vec<AccountMeta>{
+        if self.reflink == Pubkey::default(){
+            return vec![]
+        }
+        vec![
+            AccountMeta::new(self.reflink, false),
+            AccountMeta::new(self.reflink_owner.pubkey(), false)
+        ]
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// SHARED STEPS
+
+    pub async fn seller_accept(&mut self){
+        self.try_seller_accept(vec![]).await.unwrap();
+    }
+
+    pub async fn try_seller_accept(&mut self, rejected_add_ons: Vec<u8>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::SellerAcceptTransaction{
+                commission_transaction: self.commission_tx,
+                seller_market_account: self.seller_account,
+                seller_transactions: self.seller_log,
+                wallet: self.seller.pubkey()
+            },
+            orbit_commission::instruction::SellerAcceptTransaction{rejected_add_ons},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    fn commit_init_data(&self) -> orbit_commission::accounts::CommitInitData{
+        orbit_commission::accounts::CommitInitData{
+            commission_transaction: self.commission_tx,
+            seller_market_account: self.seller_account,
+            seller_transactions: self.seller_log,
+            seller_wallet: self.seller.pubkey(),
+            system_program: system_program::ID
+        }
+    }
+
+    pub async fn ship(&mut self){
+        let ix = Self::ix(self.commit_init_data(), orbit_commission::instruction::UpdateStatusToShipping{}, &[]);
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await.unwrap();
+    }
+
+    pub async fn commit_link(&mut self, link: &str) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.commit_init_data(), orbit_commission::instruction::CommitLink{link: link.to_string()}, &[]);
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn commit_init_keys(&mut self, keys: Vec<Pubkey>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.commit_init_data(), orbit_commission::instruction::CommitInitKeys{submission_keys: keys}, &[]);
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn commit_manifest(&mut self, content_hashes: Vec<[u8; 32]>, keys: Vec<Pubkey>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::CommitManifest{
+                commission_transaction: self.commission_tx,
+                seller_market_account: self.seller_account,
+                seller_transactions: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::CommitManifest{
+                key_count: keys.len() as u8,
+                content_hashes,
+                submission_keys: keys
+            },
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn close_transaction_account(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let participant = self.dispute_participant(from_seller);
+        let ix = Self::ix(
+            orbit_commission::accounts::CloseTransactionAccount{
+                commission_transaction: self.commission_tx,
+                proposer_account: participant.participant_account,
+                wallet: participant.wallet,
+                buyer_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_wallet: self.seller.pubkey()
+            },
+            orbit_commission::instruction::CloseTransactionAccount{},
+            &[]
+        );
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn transaction_len(&mut self) -> usize{
+        self.ctx.banks_client.get_account(self.commission_tx).await.unwrap().unwrap().data.len()
+    }
+
+    fn buyer_confirmation(&self) -> orbit_commission::accounts::BuyerConfirmation{
+        orbit_commission::accounts::BuyerConfirmation{
+            commission_transaction: self.commission_tx,
+            buyer_market_account: self.buyer_account,
+            buyer_transactions: self.buyer_log,
+            buyer_wallet: self.buyer.pubkey()
+        }
+    }
+
+    pub async fn confirm_delivered(&mut self){
+        let ix = Self::ix(self.buyer_confirmation(), orbit_commission::instruction::ConfirmDelivered{}, &[]);
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await.unwrap();
+    }
+
+    pub async fn confirm_accept(&mut self){
+        let ix = Self::ix(self.buyer_confirmation(), orbit_commission::instruction::ConfirmAccept{}, &[]);
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await.unwrap();
+    }
+
+    fn buyer_deny(&self) -> orbit_commission::accounts::BuyerDeny{
+        orbit_commission::accounts::BuyerDeny{
+            commission_transaction: self.commission_tx,
+            buyer_account: self.buyer_account,
+            buyer_transactions: self.buyer_log,
+            buyer_wallet: self.buyer.pubkey(),
+            commission_auth: self.commission_auth,
+            commission_program: orbit_commission::id(),
+            market_accounts_program: market_accounts::id()
+        }
+    }
+
+    pub async fn deny_accept(&mut self){
+        let ix = Self::ix(self.buyer_deny(), orbit_commission::instruction::DenyAccept{}, &[]);
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await.unwrap();
+    }
+
+    /// with no keys committed, releasing an empty set moves straight to BuyerConfirmedProduct
+    pub async fn release_keys(&mut self){
+        self.release_sealed(vec![], vec![], &[]).await.unwrap();
+    }
+
+    /// `keys` are the committed key signers, in key_arr order
+    pub async fn release_sealed(&mut self, indexes: Vec<u8>, ciphertexts: Vec<Vec<u8>>, keys: &[&Keypair]) -> std::result::Result<(), BanksClientError>{
+        let metas: Vec<AccountMeta> = keys.iter().map(|k| AccountMeta::new_readonly(k.pubkey(), true)).collect();
+        let ix = Self::ix(
+            orbit_commission::accounts::CommitSubKeys{
+                commission_transaction: self.commission_tx,
+                seller_market_account: self.seller_account,
+                seller_transactions: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::CommitSubkeys{indexes, ciphertexts},
+            &metas
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        let mut signers = vec![&seller];
+        signers.extend_from_slice(keys);
+        self.send(&[ix], &signers).await
+    }
+
+    pub async fn register_encryption_key(&mut self, encryption_key: [u8; 32]) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::RegisterEncryptionKey{
+                commission_transaction: self.commission_tx,
+                buyer_market_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey()
+            },
+            orbit_commission::instruction::RegisterEncryptionKey{encryption_key},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// PREVIEWS
+
+    pub async fn init_product_terms(&mut self, free_revisions: u8){
+        self.init_product_terms_with(free_revisions, 0, 0, vec![], false, vec![]).await
+    }
+
+    pub async fn init_product_terms_with(&mut self, free_revisions: u8, base_price: u64, min_price: u64, currency_prices: Vec<CurrencyPrice>, instant_accept: bool, add_ons: Vec<AddOn>){
+        let ix = Self::ix(
+            orbit_commission::accounts::InitProductTerms{
+                product_terms: self.product_terms,
+                commission_product: self.product,
+                seller_market_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::InitProductTerms{free_revisions, base_price, min_price, currency_prices, instant_accept, add_ons},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await.unwrap();
+    }
+
+    pub async fn commit_preview(&mut self, link: &str, content_hash: [u8; 32], rate_round: u8) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::CommitPreview{
+                commission_transaction: self.commission_tx,
+                seller_market_account: self.seller_account,
+                seller_transactions: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::CommitPreview{link: link.to_string(), content_hash, rate_round},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn request_revision(&mut self, version: u8, request: &str) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::RequestRevision{
+                commission_transaction: self.commission_tx,
+                buyer_market_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::RequestRevision{version, request: request.to_string()},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn seller_propose_rate(&mut self, new_rate_bps: u16){
+        self.propose_rate(true, new_rate_bps).await.unwrap();
+    }
+
+    fn update_rate(&self, from_seller: bool) -> orbit_commission::accounts::UpdateRate{
+        let participant = self.dispute_participant(from_seller);
+        orbit_commission::accounts::UpdateRate{
+            commission_transaction: self.commission_tx,
+            proposer_account: participant.participant_account,
+            wallet: participant.wallet,
+            system_program: system_program::ID
+        }
+    }
+
+    pub async fn propose_rate(&mut self, from_seller: bool, new_rate_bps: u16) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.update_rate(from_seller), orbit_commission::instruction::ProposeRate{new_rate_bps, note: String::new()}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn accept_rate(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.update_rate(from_seller), orbit_commission::instruction::AcceptRate{}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn reject_rate(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.update_rate(from_seller), orbit_commission::instruction::RejectRate{}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn withdraw_rate(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.update_rate(from_seller), orbit_commission::instruction::WithdrawRate{}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn migrate_rates(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::MigrateRates{
+                commission_transaction: self.commission_tx
+            },
+            orbit_commission::instruction::MigrateRates{},
+            &[]
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn propose_price(&mut self, from_seller: bool, new_price: u64) -> std::result::Result<(), BanksClientError>{
+        let participant = self.dispute_participant(from_seller);
+        let ix = Self::ix(
+            orbit_commission::accounts::ProposePrice{
+                commission_transaction: self.commission_tx,
+                proposer_account: participant.participant_account,
+                wallet: participant.wallet,
+                commission_config: self.commission_config
+            },
+            orbit_commission::instruction::ProposePrice{new_price},
+            &[]
+        );
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn accept_price_sol(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let participant = self.dispute_participant(from_seller);
+        let ix = Self::ix(
+            orbit_commission::accounts::AcceptPriceSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                acceptor_account: participant.participant_account,
+                wallet: participant.wallet,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::AcceptPriceSol{},
+            &[]
+        );
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn accept_price_spl(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let participant = self.dispute_participant(from_seller);
+        let ix = Self::ix(
+            orbit_commission::accounts::AcceptPriceSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                token_mint: self.mint.pubkey(),
+                acceptor_account: participant.participant_account,
+                wallet: participant.wallet,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                commission_auth: self.commission_auth,
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::AcceptPriceSpl{},
+            &[]
+        );
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    /// shipped, delivered and accepted by the buyer, keys released
+    pub async fn run_to_accepted(&mut self){
+        self.ship().await;
+        self.confirm_delivered().await;
+        self.confirm_accept().await;
+        self.release_keys().await;
+    }
+
+    /// shipped, delivered and denied by the buyer, upheld once the seller let the contest window pass
+    pub async fn run_to_denied(&mut self){
+        self.ship().await;
+        self.confirm_delivered().await;
+        self.deny_accept().await;
+        self.warp(CONTEST_WINDOW + 1).await;
+        self.uphold_deny().await.unwrap();
+    }
+
+    /// moves the clock forward. the blockhash is refreshed so repeated instructions aren't deduplicated
+    pub async fn warp(&mut self, seconds: i64){
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+        self.ctx.get_new_latest_blockhash().await.unwrap();
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// DISPUTES
+
+    fn dispute_participant(&self, from_seller: bool) -> orbit_commission::accounts::DisputeParticipant{
+        let (participant_account, wallet) = if from_seller{
+            (self.seller_account, self.seller.pubkey())
+        }else{
+            (self.buyer_account, self.buyer.pubkey())
+        };
+        orbit_commission::accounts::DisputeParticipant{
+            commission_transaction: self.commission_tx,
+            participant_account,
+            wallet,
+            system_program: system_program::ID
+        }
+    }
+
+    fn participant(&self, from_seller: bool) -> Keypair{
+        let wallet = if from_seller {&self.seller} else {&self.buyer};
+        Keypair::from_bytes(&wallet.to_bytes()).unwrap()
+    }
+
+    pub async fn open_dispute(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.dispute_participant(from_seller), orbit_commission::instruction::OpenDispute{}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn contest_deny(&mut self, from_seller: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.dispute_participant(from_seller), orbit_commission::instruction::ContestDeny{}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn submit_evidence(&mut self, from_seller: bool, link: &str) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.dispute_participant(from_seller), orbit_commission::instruction::SubmitEvidence{link: link.to_string()}, &[]);
+        let signer = self.participant(from_seller);
+        self.send(&[ix], &[&signer]).await
+    }
+
+    pub async fn uphold_deny(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::UpholdDeny{
+                commission_transaction: self.commission_tx,
+                buyer_account: self.buyer_account,
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                market_account_program: market_accounts::id()
+            },
+            orbit_commission::instruction::UpholdDeny{},
+            &[]
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn expire_delivery(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::ExpireDelivery{
+                commission_transaction: self.commission_tx,
+                buyer_account: self.buyer_account,
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                market_account_program: market_accounts::id()
+            },
+            orbit_commission::instruction::ExpireDelivery{},
+            &[]
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn expire_confirmation(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::ExpireConfirmation{
+                commission_transaction: self.commission_tx
+            },
+            orbit_commission::instruction::ExpireConfirmation{},
+            &[]
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// MILESTONES
+
+    pub async fn set_milestones(&mut self, amounts: Vec<u64>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::SetMilestones{
+                commission_transaction: self.commission_tx,
+                seller_market_account: self.seller_account,
+                seller_transactions: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::SetMilestones{amounts},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn commit_milestone(&mut self, index: u8, link: &str, key_mask: u64) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            self.commit_init_data(),
+            orbit_commission::instruction::CommitMilestone{index, link: link.to_string(), key_mask},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn approve_milestone_sol(&mut self, index: u8) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::ApproveMilestoneSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                commission_config: self.commission_config,
+                treasury_wallet: self.treasury.pubkey()
+            },
+            orbit_commission::instruction::ApproveMilestoneSol{index},
+            &self.reflink_metas()
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn decline_milestone(&mut self, index: u8) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(self.buyer_deny(), orbit_commission::instruction::DeclineMilestone{index}, &[]);
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// SOL
+
+    pub async fn open_sol(&mut self, price: u64, use_discount: bool){
+        self.try_open_sol(price, use_discount).await.unwrap();
+    }
+
+    fn open_sol_accounts(&self) -> orbit_commission::accounts::OpenCommissionTransactionSol{
+        orbit_commission::accounts::OpenCommissionTransactionSol{
+            commission_transaction: self.commission_tx,
+            escrow_account: self.escrow,
+            commission_product: self.product,
+            product_terms: self.product_terms,
+            buyer_transactions_log: self.buyer_log,
+            buyer_market_account: self.buyer_account,
+            buyer_wallet: self.buyer.pubkey(),
+            seller_market_account: self.seller_account,
+            seller_transactions_log: self.seller_log,
+            commission_config: self.commission_config,
+            commission_auth: self.commission_auth,
+            commission_program: orbit_commission::id(),
+            transaction_program: orbit_transaction::id(),
+            market_account_program: market_accounts::id(),
+            product_program: orbit_product::id(),
+            system_program: system_program::ID
+        }
+    }
+
+    pub async fn try_open_sol(&mut self, price: u64, use_discount: bool) -> std::result::Result<(), BanksClientError>{
+        self.open_sol_with_add_ons(price, use_discount, vec![]).await
+    }
+
+    pub async fn open_sol_with_add_ons(&mut self, price: u64, use_discount: bool, add_on_ids: Vec<u8>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            self.open_sol_accounts(),
+            orbit_commission::instruction::OpenTransactionSol{
+                seller_index: SELLER_TX_INDEX,
+                buyer_index: BUYER_TX_INDEX,
+                price,
+                use_discount,
+                add_on_ids,
+                buyer_encryption_key: self.encryption_key
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn open_instant_sol(&mut self, price: u64, use_discount: bool, add_on_ids: Vec<u8>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            self.open_sol_accounts(),
+            orbit_commission::instruction::OpenInstantSol{
+                seller_index: SELLER_TX_INDEX,
+                buyer_index: BUYER_TX_INDEX,
+                price,
+                use_discount,
+                add_on_ids,
+                buyer_encryption_key: self.encryption_key
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn fund_sol(&mut self){
+        let ix = Self::ix(
+            orbit_commission::accounts::FundEscrowSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                buyer_transactions_log: self.buyer_log,
+                buyer_market_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey()
+            },
+            orbit_commission::instruction::FundEscrowSol{},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await.unwrap();
+    }
+
+    pub async fn close_sol(&mut self){
+        let remaining = self.reflink_metas();
+        self.try_close_sol(remaining).await.unwrap();
+    }
+
+    pub async fn try_close_sol(&mut self, remaining: Vec<AccountMeta>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::CloseCommissionTransactionSol{
+                commission_transaction: self.commission_tx,
+                commission_product: self.product,
+                escrow_account: self.escrow,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                commission_config: self.commission_config,
+                treasury_wallet: self.treasury.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                market_account_program: market_accounts::id(),
+                transaction_program: orbit_transaction::id(),
+                product_program: orbit_product::id()
+            },
+            orbit_commission::instruction::CloseTransactionSol{},
+            &remaining
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn early_decline_sol(&mut self){
+        self.try_early_decline_sol().await.unwrap();
+    }
+
+    pub async fn try_early_decline_sol(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::SellerEarlyDeclineSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                market_account_program: market_accounts::id(),
+                transaction_program: orbit_transaction::id()
+            },
+            orbit_commission::instruction::SellerEarlyDeclineSol{},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// SPL
+
+    /// legacy token mint plus token accounts for buyer, seller, treasury and multisig. the buyer gets `supply`
+    pub async fn setup_tokens(&mut self, supply: u64){
+        self.setup_tokens_with(supply, spl_token::ID, None).await
+    }
+
+    /// same as setup_tokens under `token_program`. `transfer_fee` is (bps, maximum fee) and needs token-2022
+    pub async fn setup_tokens_with(&mut self, supply: u64, token_program: Pubkey, transfer_fee: Option<(u16, u64)>){
+        self.token_program = token_program;
+        let payer = self.ctx.payer.pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let mint = self.mint.pubkey();
+
+        let (mint_exts, account_exts) = match transfer_fee{
+            Some(_) => (vec![ExtensionType::TransferFeeConfig], vec![ExtensionType::TransferFeeAmount]),
+            None => (vec![], vec![])
+        };
+        let mint_len = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&mint_exts);
+        let account_len = ExtensionType::get_account_len::<spl_token_2022::state::Account>(&account_exts);
+
+        let mut ixs = vec![
+            system_instruction::create_account(&payer, &mint, rent.minimum_balance(mint_len), mint_len as u64, &token_program)
+        ];
+        if let Some((bps, max_fee)) = transfer_fee{
+            ixs.push(transfer_fee_ix::initialize_transfer_fee_config(&token_program, &mint, Some(&payer), Some(&payer), bps, max_fee).unwrap());
+        }
+        ixs.push(spl_token_2022::instruction::initialize_mint(&token_program, &mint, &payer, None, 6).unwrap());
+        for (ata, owner) in [
+            (self.buyer_ata.pubkey(), self.buyer.pubkey()),
+            (self.seller_ata.pubkey(), self.seller.pubkey()),
+            (self.treasury_ata.pubkey(), self.treasury.pubkey()),
+            (self.multisig_ata.pubkey(), self.multisig.pubkey())
+        ]{
+            ixs.push(system_instruction::create_account(&payer, &ata, rent.minimum_balance(account_len), account_len as u64, &token_program));
+            ixs.push(spl_token_2022::instruction::initialize_account(&token_program, &ata, &mint, &owner).unwrap());
+        }
+        ixs.push(spl_token_2022::instruction::mint_to(&token_program, &mint, &self.buyer_ata.pubkey(), &payer, &[], supply).unwrap());
+
+        let signers = [
+            Keypair::from_bytes(&self.mint.to_bytes()).unwrap(),
+            Keypair::from_bytes(&self.buyer_ata.to_bytes()).unwrap(),
+            Keypair::from_bytes(&self.seller_ata.to_bytes()).unwrap(),
+            Keypair::from_bytes(&self.treasury_ata.to_bytes()).unwrap(),
+            Keypair::from_bytes(&self.multisig_ata.to_bytes()).unwrap()
+        ];
+        self.send(&ixs, &signers.iter().collect::<Vec<_>>()).await.unwrap();
+    }
+
+    pub async fn open_spl(&mut self, price: u64, use_discount: bool){
+        self.try_open_spl(price, use_discount).await.unwrap();
+    }
+
+    fn open_spl_accounts(&self) -> orbit_commission::accounts::OpenCommissionTransactionSpl{
+        orbit_commission::accounts::OpenCommissionTransactionSpl{
+            commission_transaction: self.commission_tx,
+            escrow_account: self.escrow,
+            token_mint: self.mint.pubkey(),
+            commission_product: self.product,
+            product_terms: self.product_terms,
+            buyer_transactions_log: self.buyer_log,
+            buyer_market_account: self.buyer_account,
+            buyer_wallet: self.buyer.pubkey(),
+            seller_market_account: self.seller_account,
+            seller_transactions_log: self.seller_log,
+            commission_config: self.commission_config,
+            commission_auth: self.commission_auth,
+            commission_program: orbit_commission::id(),
+            market_account_program: market_accounts::id(),
+            system_program: system_program::ID,
+            token_program: self.token_program,
+            product_program: orbit_product::id(),
+            transaction_program: orbit_transaction::id(),
+            rent: anchor_lang::solana_program::sysvar::rent::ID
+        }
+    }
+
+    pub async fn try_open_spl(&mut self, price: u64, use_discount: bool) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            self.open_spl_accounts(),
+            orbit_commission::instruction::OpenTransactionSpl{
+                seller_index: SELLER_TX_INDEX,
+                buyer_index: BUYER_TX_INDEX,
+                price,
+                use_discount,
+                add_on_ids: vec![],
+                buyer_encryption_key: self.encryption_key
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn open_instant_spl(&mut self, price: u64, use_discount: bool, add_on_ids: Vec<u8>) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::OpenInstantSpl{
+                open: self.open_spl_accounts(),
+                buyer_token_account: self.buyer_ata.pubkey()
+            },
+            orbit_commission::instruction::OpenInstantSpl{
+                seller_index: SELLER_TX_INDEX,
+                buyer_index: BUYER_TX_INDEX,
+                price,
+                use_discount,
+                add_on_ids,
+                buyer_encryption_key: self.encryption_key
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn fund_spl(&mut self){
+        let ix = Self::ix(
+            orbit_commission::accounts::FundEscrowSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                token_mint: self.mint.pubkey(),
+                buyer_market_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                buyer_wallet: self.buyer.pubkey(),
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::FundEscrowSpl{},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await.unwrap();
+    }
+
+    pub async fn close_spl(&mut self){
+        let ix = Self::ix(
+            orbit_commission::accounts::CloseCommissionTransactionSpl{
+                commission_transaction: self.commission_tx,
+                commission_product: self.product,
+                escrow_account: self.escrow,
+                token_mint: self.mint.pubkey(),
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_token_account: self.seller_ata.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_config: self.commission_config,
+                treasury_ata: self.treasury_ata.pubkey(),
+                market_account_program: market_accounts::id(),
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id(),
+                token_program: self.token_program,
+                product_program: orbit_product::id()
+            },
+            orbit_commission::instruction::CloseTransactionSpl{},
+            &[]
+        );
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    pub async fn early_decline_spl(&mut self){
+        let ix = Self::ix(
+            orbit_commission::accounts::SellerEarlyDeclineSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                token_mint: self.mint.pubkey(),
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                buyer_wallet: self.buyer.pubkey(),
+                seller_market_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_token_account: self.seller_ata.pubkey(),
+                seller_wallet: self.seller.pubkey(),
+                commission_auth: self.commission_auth,
+                market_account_program: market_accounts::id(),
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id(),
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::SellerEarlyDeclineSpl{},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await.unwrap();
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// QUOTES
+
+    pub fn quote(&self) -> Pubkey{
+        Pubkey::find_program_address(
+            &[b"commission_quote", self.product.as_ref(), &BUYER_ID.to_le_bytes()],
+            &orbit_commission::id()
+        ).0
+    }
+
+    pub async fn now(&mut self) -> i64{
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+
+    pub async fn create_quote(&mut self, price: u64, currency: Pubkey, deadline: i64, delivery_window: i64, scope_hash: [u8; 32]) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::CreateQuote{
+                quote: self.quote(),
+                commission_product: self.product,
+                seller_market_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::CreateQuote{buyer: BUYER_ID, price, currency, deadline, delivery_window, scope_hash},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn withdraw_quote(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::WithdrawQuote{
+                quote: self.quote(),
+                seller_market_account: self.seller_account,
+                seller_wallet: self.seller.pubkey()
+            },
+            orbit_commission::instruction::WithdrawQuote{},
+            &[]
+        );
+        let seller = Keypair::from_bytes(&self.seller.to_bytes()).unwrap();
+        self.send(&[ix], &[&seller]).await
+    }
+
+    pub async fn accept_quote_sol(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::AcceptQuoteSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                quote: self.quote(),
+                commission_product: self.product,
+                product_terms: self.product_terms,
+                buyer_transactions_log: self.buyer_log,
+                buyer_market_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_market_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                seller_transactions_log: self.seller_log,
+                commission_config: self.commission_config,
+                commission_auth: self.commission_auth,
+                transaction_program: orbit_transaction::id(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::AcceptQuoteSol{
+                seller_tx_index: SELLER_TX_INDEX,
+                buyer_tx_index: BUYER_TX_INDEX
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn accept_quote_spl(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::AcceptQuoteSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                quote: self.quote(),
+                token_mint: self.mint.pubkey(),
+                commission_product: self.product,
+                product_terms: self.product_terms,
+                buyer_transactions_log: self.buyer_log,
+                buyer_market_account: self.buyer_account,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                buyer_wallet: self.buyer.pubkey(),
+                seller_market_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                seller_transactions_log: self.seller_log,
+                commission_config: self.commission_config,
+                commission_auth: self.commission_auth,
+                transaction_program: orbit_transaction::id(),
+                system_program: system_program::ID,
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::AcceptQuoteSpl{
+                seller_tx_index: SELLER_TX_INDEX,
+                buyer_tx_index: BUYER_TX_INDEX
+            },
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    //////////////////////////////////////////////////////////////////////////
+    /// CANCEL
+
+    pub async fn buyer_cancel_sol(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::BuyerCancelSol{
+                commission_transaction: self.commission_tx,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                commission_auth: self.commission_auth,
+                market_account_program: market_accounts::id(),
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id()
+            },
+            orbit_commission::instruction::BuyerCancelSol{},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn buyer_cancel_spl(&mut self) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::BuyerCancelSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                commission_auth: self.commission_auth,
+                market_account_program: market_accounts::id(),
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id(),
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::BuyerCancelSpl{},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn tip_sol(&mut self, amount: u64) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::TipSol{
+                commission_transaction: self.commission_tx,
+                buyer_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_wallet: self.seller.pubkey(),
+                commission_config: self.commission_config,
+                multisig_wallet: self.multisig.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::TipSol{amount},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn tip_spl(&mut self, amount: u64) -> std::result::Result<(), BanksClientError>{
+        let ix = Self::ix(
+            orbit_commission::accounts::TipSpl{
+                commission_transaction: self.commission_tx,
+                token_mint: self.mint.pubkey(),
+                buyer_account: self.buyer_account,
+                buyer_wallet: self.buyer.pubkey(),
+                buyer_token_account: self.buyer_ata.pubkey(),
+                seller_account: self.seller_account,
+                seller_token_account: self.seller_ata.pubkey(),
+                commission_config: self.commission_config,
+                multisig_token_account: self.multisig_ata.pubkey(),
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::TipSpl{amount},
+            &[]
+        );
+        let buyer = Keypair::from_bytes(&self.buyer.to_bytes()).unwrap();
+        self.send(&[ix], &[&buyer]).await
+    }
+
+    pub async fn leave_text_review(&mut self, from_seller: bool, rating: u8, review_hash: [u8; 32]) -> std::result::Result<(), BanksClientError>{
+        let (reviewer, reviewed) = if from_seller {(&self.seller, self.buyer_account)} else {(&self.buyer, self.seller_account)};
+        let reviewer = Keypair::from_bytes(&reviewer.to_bytes()).unwrap();
+        let ix = Self::ix(
+            orbit_commission::accounts::LeaveReview{
+                commission_transaction: self.commission_tx,
+                reviewed_account: reviewed,
+                reviewer: market_account_address(&reviewer.pubkey()),
+                wallet: reviewer.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                accounts_program: market_accounts::id()
+            },
+            orbit_commission::instruction::LeaveTextReview{rating, review_hash},
+            &[]
+        );
+        self.send(&[ix], &[&reviewer]).await
+    }
+
+    pub async fn propose_mutual_cancel(&mut self, from_seller: bool, seller_bps: u16) -> std::result::Result<(), BanksClientError>{
+        let (proposer, account) = if from_seller {(&self.seller, self.seller_account)} else {(&self.buyer, self.buyer_account)};
+        let proposer = Keypair::from_bytes(&proposer.to_bytes()).unwrap();
+        let ix = Self::ix(
+            orbit_commission::accounts::ProposeMutualCancel{
+                commission_transaction: self.commission_tx,
+                proposer_account: account,
+                wallet: proposer.pubkey(),
+                system_program: system_program::ID
+            },
+            orbit_commission::instruction::ProposeMutualCancel{seller_bps},
+            &[]
+        );
+        self.send(&[ix], &[&proposer]).await
+    }
+
+    pub async fn accept_mutual_cancel_sol(&mut self, from_seller: bool, seller_bps: u16) -> std::result::Result<(), BanksClientError>{
+        let acceptor = Keypair::from_bytes(&(if from_seller {&self.seller} else {&self.buyer}).to_bytes()).unwrap();
+        let ix = Self::ix(
+            orbit_commission::accounts::MutualCancelSol{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_wallet: self.seller.pubkey(),
+                acceptor_wallet: acceptor.pubkey(),
+                commission_config: self.commission_config,
+                treasury_wallet: self.treasury.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id()
+            },
+            orbit_commission::instruction::AcceptMutualCancelSol{seller_bps},
+            &[]
+        );
+        self.send(&[ix], &[&acceptor]).await
+    }
+
+    pub async fn accept_mutual_cancel_spl(&mut self, from_seller: bool, seller_bps: u16) -> std::result::Result<(), BanksClientError>{
+        let acceptor = Keypair::from_bytes(&(if from_seller {&self.seller} else {&self.buyer}).to_bytes()).unwrap();
+        let ix = Self::ix(
+            orbit_commission::accounts::MutualCancelSpl{
+                commission_transaction: self.commission_tx,
+                escrow_account: self.escrow,
+                token_mint: self.mint.pubkey(),
+                buyer_account: self.buyer_account,
+                buyer_transactions_log: self.buyer_log,
+                buyer_token_account: self.buyer_ata.pubkey(),
+                buyer_wallet: self.buyer.pubkey(),
+                seller_account: self.seller_account,
+                seller_transactions_log: self.seller_log,
+                seller_token_account: self.seller_ata.pubkey(),
+                acceptor_wallet: acceptor.pubkey(),
+                commission_config: self.commission_config,
+                treasury_ata: self.treasury_ata.pubkey(),
+                commission_auth: self.commission_auth,
+                commission_program: orbit_commission::id(),
+                transaction_program: orbit_transaction::id(),
+                token_program: self.token_program
+            },
+            orbit_commission::instruction::AcceptMutualCancelSpl{seller_bps},
+            &[]
+        );
+        self.send(&[ix], &[&acceptor]).await
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use orbit_commission::{BuyerDecisionState, CONFIRMATION_WINDOW, DELIVERY_WINDOW};
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::Signer;
+
+const SUPPLY: u64 = PRICE * 2;
+
+#[tokio::test]
+async fn unshipped_work_refunds_the_buyer_after_the_delivery_window(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.expire_delivery().await.is_err());
+    f.warp(DELIVERY_WINDOW - 60).await;
+    assert!(f.expire_delivery().await.is_err());
+
+    f.warp(61).await;
+    f.expire_delivery().await.unwrap();
+    let tx = f.transaction().await;
+    assert!(tx.final_decision == BuyerDecisionState::Declined);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerConfirmedProduct);
+
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    f.close_sol().await;
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE);
+    assert_eq!(f.balance(f.seller.pubkey()).await, seller);
+    assert_eq!(f.balance(f.escrow).await, 0);
+}
+
+#[tokio::test]
+async fn shipping_stops_the_delivery_crank(){
+    let mut f = Fixture::funded_sol().await;
+    f.ship().await;
+    f.warp(DELIVERY_WINDOW + 1).await;
+    assert!(f.expire_delivery().await.is_err());
+}
+
+#[tokio::test]
+async fn undecided_buyers_release_the_escrow_after_the_confirmation_window(){
+    let mut f = Fixture::funded_sol().await;
+    // nothing shipped yet, so there is nothing for the buyer to decide on
+    assert!(f.expire_confirmation().await.is_err());
+
+    f.ship().await;
+    f.confirm_delivered().await;
+    f.warp(CONFIRMATION_WINDOW - 60).await;
+    assert!(f.expire_confirmation().await.is_err());
+
+    f.warp(61).await;
+    f.expire_confirmation().await.unwrap();
+    let tx = f.transaction().await;
+    assert!(tx.final_decision == BuyerDecisionState::Accept);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerConfirmedProduct);
+
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.close_sol().await;
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, PRICE - fee);
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, fee);
+}
+
+#[tokio::test]
+async fn buyer_decisions_stop_the_confirmation_crank(){
+    let mut f = Fixture::funded_sol().await;
+    f.ship().await;
+    f.confirm_delivered().await;
+    f.confirm_accept().await;
+    f.warp(CONFIRMATION_WINDOW + 1).await;
+    assert!(f.expire_confirmation().await.is_err());
+}
+
+#[tokio::test]
+async fn spl_escrows_follow_the_cranks(){
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    f.warp(DELIVERY_WINDOW + 1).await;
+    f.expire_delivery().await.unwrap();
+    f.close_spl().await;
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY);
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, 0);
+
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    f.ship().await;
+    f.warp(CONFIRMATION_WINDOW + 1).await;
+    f.expire_confirmation().await.unwrap();
+    f.close_spl().await;
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, PRICE - fee);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, fee);
+}
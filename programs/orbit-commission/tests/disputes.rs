@@ -0,0 +1,84 @@
+mod common;
+
+use common::*;
+use orbit_commission::{BuyerDecisionState, DisputeState, CONTEST_WINDOW};
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::Signer;
+
+async fn denied() -> Fixture{
+    let mut f = Fixture::funded_sol().await;
+    f.ship().await;
+    f.confirm_delivered().await;
+    f.deny_accept().await;
+    f
+}
+
+#[tokio::test]
+async fn deny_cant_close_while_the_seller_can_contest(){
+    let mut f = denied().await;
+    let tx = f.transaction().await;
+    assert!(tx.dispute_state == DisputeState::Denied);
+    assert!(tx.final_decision == BuyerDecisionState::Null);
+    assert!(tx.contest_deadline > f.now().await);
+
+    let remaining = f.reflink_metas();
+    assert!(f.try_close_sol(remaining).await.is_err());
+    assert!(f.uphold_deny().await.is_err());
+    assert!(f.expire_confirmation().await.is_err());
+    assert!(f.try_early_decline_sol().await.is_err());
+    assert_eq!(f.balance(f.escrow).await, PRICE);
+}
+
+#[tokio::test]
+async fn uncontested_deny_is_upheld_and_refunded(){
+    let mut f = denied().await;
+    f.warp(CONTEST_WINDOW + 1).await;
+    assert!(f.contest_deny(true).await.is_err());
+    f.uphold_deny().await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.final_decision == BuyerDecisionState::Declined);
+    assert!(tx.dispute_state == DisputeState::None);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerConfirmedProduct);
+
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    f.close_sol().await;
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE);
+}
+
+#[tokio::test]
+async fn contested_deny_goes_to_dispute(){
+    let mut f = denied().await;
+    assert!(f.contest_deny(false).await.is_err());
+    f.contest_deny(true).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.dispute_state == DisputeState::Open);
+    assert!(tx.final_decision == BuyerDecisionState::Null);
+
+    f.submit_evidence(true, "https://example.com/final.png").await.unwrap();
+    f.submit_evidence(false, "https://example.com/brief.png").await.unwrap();
+
+    // the arbiter decides now, not the clock
+    f.warp(CONTEST_WINDOW + 1).await;
+    assert!(f.uphold_deny().await.is_err());
+    let remaining = f.reflink_metas();
+    assert!(f.try_close_sol(remaining).await.is_err());
+}
+
+#[tokio::test]
+async fn disputes_only_open_once(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_sol().await;
+    assert!(f.open_dispute(false).await.is_err());
+
+    f.ship().await;
+    f.open_dispute(false).await.unwrap();
+    assert!(f.open_dispute(true).await.is_err());
+    // the seller can't walk away from an open dispute either
+    assert!(f.try_early_decline_sol().await.is_err());
+    assert!(f.contest_deny(true).await.is_err());
+    assert!(f.transaction().await.dispute_state == DisputeState::Open);
+}
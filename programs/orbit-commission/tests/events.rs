@@ -0,0 +1,134 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use orbit_commission::CommissionTransition;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::{Keypair, Signer};
+
+const HALF: u64 = PRICE / 2;
+
+#[tokio::test]
+async fn every_step_emits_its_transition(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.open_sol(PRICE, false).await;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::Opened);
+    assert!(event.state == TransactionState::Opened);
+    assert_eq!(event.transaction, f.commission_tx);
+    assert_eq!((event.buyer, event.seller, event.price), (BUYER_ID, SELLER_ID, PRICE));
+
+    f.seller_accept().await;
+    assert!(f.transition().transition == CommissionTransition::SellerAccepted);
+    f.fund_sol().await;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::Funded);
+    assert!(event.state == TransactionState::BuyerFunded);
+
+    f.ship().await;
+    assert!(f.transition().state == TransactionState::Shipped);
+    f.confirm_delivered().await;
+    assert!(f.transition().transition == CommissionTransition::Delivered);
+    f.confirm_accept().await;
+    assert!(f.transition().transition == CommissionTransition::Accepted);
+    f.release_keys().await;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::SubkeysReleased{pending_keys: 0});
+    assert!(event.state == TransactionState::BuyerConfirmedProduct);
+
+    f.close_sol().await;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::Closed);
+    assert!(event.state == TransactionState::Closed);
+
+    f.leave_text_review(false, 4, [7; 32]).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::Reviewed{rating: 4});
+}
+
+#[tokio::test]
+async fn opens_report_the_encryption_key(){
+    let mut f = Fixture::new(Options{encryption_key: [7u8; 32], ..Options::default()}).await;
+    f.open_sol(PRICE, false).await;
+    let kinds: Vec<CommissionTransition> = f.transitions().iter().map(|event| event.transition).collect();
+    assert!(kinds == vec![CommissionTransition::Opened, CommissionTransition::EncryptionKeyRegistered]);
+}
+
+#[tokio::test]
+async fn rates_and_previews_carry_their_round(){
+    let mut f = Fixture::funded_sol().await;
+    f.commit_preview("https://preview/1", [1u8; 32], 0).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::PreviewCommitted{version: 1, rate_round: 0});
+
+    f.seller_propose_rate(2000).await;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::RateProposed{round: 1, rate_bps: 2000});
+    assert_eq!(event.preview_rate_bps, 2000);
+
+    f.accept_rate(false).await.unwrap();
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::RateAccepted{round: 1});
+    assert_eq!(event.close_rate_bps, 2000);
+
+    f.commit_preview("https://preview/2", [2u8; 32], 1).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::PreviewCommitted{version: 2, rate_round: 1});
+    f.request_revision(2, "https://notes/2").await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::RevisionRequested{version: 2});
+}
+
+#[tokio::test]
+async fn price_changes_carry_the_new_price(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_price(true, PRICE * 2).await.unwrap();
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::PriceProposed{price: PRICE * 2});
+    assert_eq!(event.price, PRICE);
+
+    f.accept_price_sol(false).await.unwrap();
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::PriceAccepted{price: PRICE * 2});
+    assert_eq!(event.price, PRICE * 2);
+}
+
+#[tokio::test]
+async fn milestone_payouts_carry_index_and_amount(){
+    let mut f = Fixture::new(Options{encryption_key: [7u8; 32], ..Options::default()}).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    let keys: Vec<Pubkey> = (0..2).map(|_| Keypair::new())
+        .map(|k| Pubkey::find_program_address(&[k.pubkey().as_ref()], &orbit_commission::id()).0)
+        .collect();
+    f.commit_manifest(vec![[1u8; 32], [2u8; 32]], keys).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::ManifestCommitted{key_count: 2});
+    f.set_milestones(vec![HALF, HALF]).await.unwrap();
+    f.fund_sol().await;
+
+    f.commit_milestone(1, "https://milestone/2", 0b10).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::MilestoneCommitted{index: 1});
+    f.approve_milestone_sol(1).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::MilestoneApproved{index: 1, amount: HALF});
+}
+
+#[tokio::test]
+async fn mutual_cancels_carry_both_shares(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_mutual_cancel(false, 4000).await.unwrap();
+    assert!(f.transition().transition == CommissionTransition::MutualCancelProposed{seller_bps: 4000});
+
+    f.accept_mutual_cancel_sol(true, 4000).await.unwrap();
+    let seller_share = PRICE * 4000 / 10000;
+    let fee = seller_share * FEE_BPS as u64 / 10000;
+    let event = f.transition();
+    assert!(event.transition == CommissionTransition::MutualCancelled{seller: seller_share - fee, buyer: PRICE - seller_share});
+    assert!(event.state == TransactionState::Closed);
+}
+
+#[tokio::test]
+async fn rejected_steps_emit_nothing(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.propose_price(false, PRICE * 2).await.is_err());
+    assert!(f.transitions().is_empty());
+    assert!(f.accept_rate(false).await.is_err());
+    assert!(f.transitions().is_empty());
+    assert!(f.leave_text_review(false, 5, [7; 32]).await.is_err());
+    assert!(f.transitions().is_empty());
+}
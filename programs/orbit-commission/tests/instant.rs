@@ -0,0 +1,60 @@
+mod common;
+
+use common::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::Signer;
+
+const SUPPLY: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn instant_sol_opens_funded(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, 0, 0, vec![], true, vec![]).await;
+    let escrow_before = f.balance(f.escrow).await;
+    f.open_instant_sol(PRICE, false, vec![]).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerFunded);
+    assert!(tx.metadata.funded);
+    assert!(tx.delivery_deadline > 0);
+    assert_eq!(f.balance(f.escrow).await - escrow_before, PRICE);
+
+    f.run_to_accepted().await;
+    f.close_sol().await;
+}
+
+#[tokio::test]
+async fn instant_spl_opens_funded(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(SUPPLY).await;
+    f.init_product_terms_with(0, 0, 0, vec![], true, vec![]).await;
+    f.open_instant_spl(PRICE, false, vec![]).await.unwrap();
+
+    assert!(f.transaction().await.metadata.transaction_state == TransactionState::BuyerFunded);
+    assert_eq!(f.token_balance(f.escrow).await, PRICE);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - PRICE);
+}
+
+#[tokio::test]
+async fn instant_spl_funds_from_the_buyers_own_account(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(SUPPLY).await;
+    f.init_product_terms_with(0, 0, 0, vec![], true, vec![]).await;
+
+    std::mem::swap(&mut f.buyer_ata, &mut f.seller_ata);
+    assert!(f.open_instant_spl(PRICE, false, vec![]).await.is_err());
+    std::mem::swap(&mut f.buyer_ata, &mut f.seller_ata);
+    f.open_instant_spl(PRICE, false, vec![]).await.unwrap();
+}
+
+#[tokio::test]
+async fn instant_needs_the_product_flag(){
+    let mut f = Fixture::new(Options::default()).await;
+    assert!(f.open_instant_sol(PRICE, false, vec![]).await.is_err());
+
+    f.init_product_terms(0).await;
+    assert!(f.open_instant_sol(PRICE, false, vec![]).await.is_err());
+
+    // the regular flow still works
+    f.open_sol(PRICE, false).await;
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use anchor_lang::system_program;
+use common::*;
+use orbit_commission::CurrencyPrice;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn open_respects_the_listing_floor(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, PRICE, PRICE / 2, vec![], false, vec![]).await;
+
+    assert!(f.try_open_sol(PRICE / 2 - 1, false).await.is_err());
+    f.open_sol(PRICE / 2, false).await;
+    assert_eq!(f.transaction().await.metadata.transaction_price, PRICE / 2);
+}
+
+#[tokio::test]
+async fn base_price_is_the_floor_without_a_minimum(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, PRICE, 0, vec![], false, vec![]).await;
+
+    assert!(f.try_open_sol(PRICE - 1, false).await.is_err());
+    f.open_sol(PRICE, false).await;
+}
+
+#[tokio::test]
+async fn open_rejects_unlisted_currency(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(PRICE * 2).await;
+    f.init_product_terms_with(0, PRICE, 0, vec![CurrencyPrice{mint: system_program::ID, price: PRICE}], false, vec![]).await;
+
+    assert!(f.try_open_spl(PRICE, false).await.is_err());
+    f.open_sol(PRICE, false).await;
+}
+
+#[tokio::test]
+async fn each_currency_has_its_own_price(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(PRICE * 2).await;
+    let mint = f.mint.pubkey();
+    f.init_product_terms_with(0, PRICE, 0, vec![
+        CurrencyPrice{mint: system_program::ID, price: PRICE},
+        CurrencyPrice{mint, price: PRICE / 4}
+    ], false, vec![]).await;
+
+    assert!(f.try_open_spl(PRICE / 4 - 1, false).await.is_err());
+    assert!(f.try_open_spl(PRICE / 4 + 1, false).await.is_err());
+    f.open_spl(PRICE / 4, false).await;
+    let tx = f.transaction().await;
+    assert_eq!(tx.metadata.transaction_price, PRICE / 4);
+    assert_eq!(tx.metadata.currency, mint);
+}
+
+#[tokio::test]
+async fn sol_price_ignores_token_listing(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms_with(0, 0, 0, vec![
+        CurrencyPrice{mint: system_program::ID, price: PRICE},
+        CurrencyPrice{mint: f.mint.pubkey(), price: PRICE / 4}
+    ], false, vec![]).await;
+
+    assert!(f.try_open_sol(PRICE / 4, false).await.is_err());
+    assert!(f.try_open_sol(PRICE * 2, false).await.is_err());
+    f.open_sol(PRICE, false).await;
+}
+
+#[tokio::test]
+async fn seller_quotes_bypass_the_listing(){
+    let mut f = Fixture::new(Options::default()).await;
+    let mint = f.mint.pubkey();
+    f.init_product_terms_with(0, PRICE, 0, vec![CurrencyPrice{mint, price: PRICE}], false, vec![]).await;
+
+    let deadline = f.now().await + 60 * 60;
+    f.create_quote(PRICE / 10, system_program::ID, deadline, 60 * 60 * 24, [0u8; 32]).await.unwrap();
+    f.accept_quote_sol().await.unwrap();
+    assert_eq!(f.transaction().await.metadata.transaction_price, PRICE / 10);
+}
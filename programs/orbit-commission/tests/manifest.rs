@@ -0,0 +1,91 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn manifest_is_committed_once(){
+    let mut f = Fixture::accepted().await;
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let hashes = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
+    f.commit_manifest(hashes.clone(), keys.clone()).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.keys_committed);
+    assert_eq!(tx.key_arr, keys);
+    assert_eq!(tx.content_hashes, hashes);
+    assert_eq!(tx.num_keys, 0b111);
+
+    // neither path can replace the committed keys
+    assert!(f.commit_manifest(vec![[9u8; 32]], vec![Pubkey::new_unique()]).await.is_err());
+    f.fund_sol().await;
+    assert!(f.commit_init_keys(vec![Pubkey::new_unique()]).await.is_err());
+    assert_eq!(f.transaction().await.key_arr, keys);
+}
+
+#[tokio::test]
+async fn manifest_lengths_must_match(){
+    let mut f = Fixture::accepted().await;
+    let keys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+    assert!(f.commit_manifest(vec![[1u8; 32]], keys).await.is_err());
+    assert!(!f.transaction().await.keys_committed);
+}
+
+/// manifest of two keys, funded and accepted by the buyer
+async fn delivered(encryption_key: [u8; 32]) -> (Fixture, Vec<Keypair>){
+    let mut f = Fixture::new(Options{encryption_key, ..Options::default()}).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    let keys: Vec<Keypair> = (0..2).map(|_| Keypair::new()).collect();
+    let committed = keys.iter().map(|k| Pubkey::find_program_address(&[k.pubkey().as_ref()], &orbit_commission::id()).0).collect();
+    f.commit_manifest(vec![[1u8; 32], [2u8; 32]], committed).await.unwrap();
+    f.fund_sol().await;
+    f.ship().await;
+    f.confirm_delivered().await;
+    f.confirm_accept().await;
+    (f, keys)
+}
+
+#[tokio::test]
+async fn keys_are_released_sealed(){
+    let (mut f, keys) = delivered([7u8; 32]).await;
+    let committed = f.transaction().await.key_arr;
+    let signers: Vec<&Keypair> = keys.iter().collect();
+
+    f.release_sealed(vec![0], vec![vec![0xaa; 48]], &signers).await.unwrap();
+    assert!(f.release_sealed(vec![0], vec![vec![0xbb; 48]], &signers).await.is_err());
+    f.release_sealed(vec![1], vec![vec![0xcc; 48]], &signers).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.buyer_encryption_key, [7u8; 32]);
+    // key_arr keeps the commitments, the plaintext signer never lands on chain
+    assert_eq!(tx.key_arr, committed);
+    assert_eq!(tx.sealed_keys.len(), 2);
+    assert_eq!(tx.sealed_keys[0].ciphertext, vec![0xaa; 48]);
+    assert_eq!(tx.sealed_keys[1].index, 1);
+    assert_eq!(tx.num_keys, 0);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerConfirmedProduct);
+}
+
+#[tokio::test]
+async fn release_needs_buyer_encryption_key(){
+    let (mut f, keys) = delivered([0; 32]).await;
+    let signers: Vec<&Keypair> = keys.iter().collect();
+    assert!(f.release_sealed(vec![0], vec![vec![0xaa; 48]], &signers).await.is_err());
+    assert!(f.register_encryption_key([7u8; 32]).await.is_err());
+    assert_eq!(f.transaction().await.num_keys, 0b11);
+}
+
+#[tokio::test]
+async fn encryption_key_comes_with_the_open(){
+    let mut f = Fixture::new(Options{encryption_key: [7u8; 32], ..Options::default()}).await;
+    f.open_sol(PRICE, false).await;
+    assert_eq!(f.transaction().await.buyer_encryption_key, [7u8; 32]);
+
+    // rotating it is still allowed before anything is sealed
+    assert!(f.register_encryption_key([0; 32]).await.is_err());
+    f.register_encryption_key([8u8; 32]).await.unwrap();
+    assert_eq!(f.transaction().await.buyer_encryption_key, [8u8; 32]);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use orbit_commission::{BuyerDecisionState, DisputeState, MilestoneState};
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::{Keypair, Signer};
+
+const HALF: u64 = PRICE / 2;
+
+/// two equal milestones over a manifest of two keys, funded
+async fn planned() -> (Fixture, Vec<Keypair>){
+    let mut f = Fixture::new(Options{encryption_key: [7u8; 32], ..Options::default()}).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    let keys: Vec<Keypair> = (0..2).map(|_| Keypair::new()).collect();
+    let committed = keys.iter().map(|k| Pubkey::find_program_address(&[k.pubkey().as_ref()], &orbit_commission::id()).0).collect();
+    f.commit_manifest(vec![[1u8; 32], [2u8; 32]], committed).await.unwrap();
+    f.set_milestones(vec![HALF, HALF]).await.unwrap();
+    f.fund_sol().await;
+    (f, keys)
+}
+
+#[tokio::test]
+async fn declined_milestones_go_back_to_the_seller(){
+    let (mut f, _) = planned().await;
+    f.commit_milestone(0, "https://milestone/1", 0b01).await.unwrap();
+    f.decline_milestone(0).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.milestones[0].state == MilestoneState::Declined);
+    assert!(tx.final_decision == BuyerDecisionState::Null);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerFunded);
+    assert_eq!(f.balance(f.escrow).await, PRICE);
+
+    // nothing to approve until the seller resubmits
+    assert!(f.approve_milestone_sol(0).await.is_err());
+    f.commit_milestone(0, "https://milestone/1b", 0b01).await.unwrap();
+    let seller = f.balance(f.seller.pubkey()).await;
+    f.approve_milestone_sol(0).await.unwrap();
+
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, HALF - HALF * FEE_BPS as u64 / 10000);
+    assert!(f.transaction().await.milestones[0].state == MilestoneState::Approved);
+}
+
+#[tokio::test]
+async fn declined_milestones_can_be_disputed(){
+    let (mut f, _) = planned().await;
+    f.commit_milestone(0, "https://milestone/1", 0b01).await.unwrap();
+    assert!(f.open_dispute(true).await.is_err());
+
+    f.decline_milestone(0).await.unwrap();
+    f.open_dispute(true).await.unwrap();
+    assert!(f.transaction().await.dispute_state == DisputeState::Open);
+
+    // the arbiter settles the plan from here
+    f.commit_milestone(1, "https://milestone/2", 0b10).await.unwrap();
+    assert!(f.approve_milestone_sol(1).await.is_err());
+    assert!(f.decline_milestone(1).await.is_err());
+}
+
+#[tokio::test]
+async fn key_masks_only_name_committed_keys(){
+    let (mut f, _) = planned().await;
+    assert!(f.commit_milestone(0, "https://milestone/1", 0b100).await.is_err());
+    assert!(f.commit_milestone(0, "https://milestone/1", 1 << 63).await.is_err());
+    f.commit_milestone(0, "https://milestone/1", 0b11).await.unwrap();
+    assert_eq!(f.transaction().await.milestones[0].key_mask, 0b11);
+}
+
+#[tokio::test]
+async fn approving_every_milestone_releases_uncovered_keys(){
+    let (mut f, keys) = planned().await;
+    let signers: Vec<&Keypair> = keys.iter().collect();
+    f.commit_milestone(0, "https://milestone/1", 0b01).await.unwrap();
+    f.commit_milestone(1, "https://milestone/2", 0).await.unwrap();
+
+    f.approve_milestone_sol(0).await.unwrap();
+    f.release_sealed(vec![0], vec![vec![0xaa; 48]], &signers).await.unwrap();
+    assert!(f.release_sealed(vec![1], vec![vec![0xbb; 48]], &signers).await.is_err());
+
+    f.approve_milestone_sol(1).await.unwrap();
+    let tx = f.transaction().await;
+    assert!(tx.final_decision == BuyerDecisionState::Accept);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerFunded);
+
+    f.release_sealed(vec![1], vec![vec![0xbb; 48]], &signers).await.unwrap();
+    let tx = f.transaction().await;
+    assert_eq!(tx.num_keys, 0);
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerConfirmedProduct);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn buyer_proposes_seller_accepts_sol(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_mutual_cancel(false, 4000).await.unwrap();
+    assert_eq!(f.transaction().await.cancel_offer.unwrap().seller_bps, 4000);
+
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.accept_mutual_cancel_sol(true, 4000).await.unwrap();
+
+    let seller_share = PRICE * 4000 / 10000;
+    let fee = seller_share * FEE_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, fee);
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE - seller_share);
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, seller_share - fee);
+    assert_eq!(f.balance(f.escrow).await, 0);
+
+    let tx = f.transaction().await;
+    assert!(tx.metadata.transaction_state == TransactionState::Closed);
+    assert!(tx.cancel_offer.is_none());
+}
+
+#[tokio::test]
+async fn proposer_cant_accept_their_own_offer(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_mutual_cancel(true, 7000).await.unwrap();
+    assert!(f.accept_mutual_cancel_sol(true, 7000).await.is_err());
+}
+
+#[tokio::test]
+async fn accept_has_to_match_the_offer(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.accept_mutual_cancel_sol(true, 5000).await.is_err());
+
+    f.propose_mutual_cancel(false, 5000).await.unwrap();
+    f.propose_mutual_cancel(true, 9000).await.unwrap();
+    assert!(f.accept_mutual_cancel_sol(false, 5000).await.is_err());
+    f.accept_mutual_cancel_sol(false, 9000).await.unwrap();
+}
+
+#[tokio::test]
+async fn split_cant_exceed_the_escrow(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.propose_mutual_cancel(false, 10001).await.is_err());
+}
+
+#[tokio::test]
+async fn unfunded_transactions_use_buyer_cancel(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    assert!(f.propose_mutual_cancel(false, 0).await.is_err());
+}
+
+#[tokio::test]
+async fn seller_proposes_buyer_accepts_spl(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(PRICE).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+
+    f.propose_mutual_cancel(true, 2500).await.unwrap();
+    f.accept_mutual_cancel_spl(false, 2500).await.unwrap();
+
+    let seller_share = PRICE * 2500 / 10000;
+    let fee = seller_share * FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, seller_share - fee);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, fee);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, PRICE - seller_share);
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_none());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::*;
+use orbit_commission::RateOfferStatus;
+
+#[tokio::test]
+async fn preview_rounds_are_versioned(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.init_product_terms(1).await;
+    f.open_sol(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_sol().await;
+    assert_eq!(f.transaction().await.free_revisions, 1);
+
+    f.commit_preview("https://preview/1", [1u8; 32], 0).await.unwrap();
+    // only the newest round takes annotations, and only once
+    assert!(f.request_revision(2, "https://notes/1").await.is_err());
+    f.request_revision(1, "https://notes/1").await.unwrap();
+    assert!(f.request_revision(1, "https://notes/again").await.is_err());
+    f.commit_preview("https://preview/2", [2u8; 32], 0).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.preview_rounds.len(), 2);
+    assert_eq!(tx.preview_rounds[0].version, 1);
+    assert_eq!(tx.preview_rounds[0].revision_request, "https://notes/1");
+    assert_eq!(tx.preview_rounds[1].version, 2);
+    assert_eq!(tx.preview_rounds[1].content_hash, [2u8; 32]);
+    assert!(tx.preview_rounds[1].revision_request.is_empty());
+    assert_eq!(tx.preview_address, "https://preview/2");
+}
+
+#[tokio::test]
+async fn paid_revisions_need_a_seller_rate(){
+    let mut f = Fixture::funded_sol().await;
+    assert_eq!(f.transaction().await.free_revisions, 0);
+
+    f.commit_preview("https://preview/1", [1u8; 32], 0).await.unwrap();
+    assert!(f.commit_preview("https://preview/2", [2u8; 32], 0).await.is_err());
+    // a round can't point at an offer that was never made
+    assert!(f.commit_preview("https://preview/2", [2u8; 32], 1).await.is_err());
+
+    // the buyer has to take the offer first, and it only pays for one round
+    f.seller_propose_rate(2000).await;
+    assert!(f.commit_preview("https://preview/2", [2u8; 32], 1).await.is_err());
+    f.accept_rate(false).await.unwrap();
+    f.commit_preview("https://preview/2", [2u8; 32], 1).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.preview_rounds[1].rate_round, 1);
+    assert!(tx.rate_offers[0].status == RateOfferStatus::Consumed);
+    assert!(f.commit_preview("https://preview/3", [3u8; 32], 1).await.is_err());
+}
+
+#[tokio::test]
+async fn buyer_offers_dont_pay_for_rounds(){
+    let mut f = Fixture::funded_sol().await;
+    f.commit_preview("https://preview/1", [1u8; 32], 0).await.unwrap();
+    f.propose_rate(false, 2000).await.unwrap();
+    f.accept_rate(true).await.unwrap();
+    assert!(f.commit_preview("https://preview/2", [2u8; 32], 1).await.is_err());
+}
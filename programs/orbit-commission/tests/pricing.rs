@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+
+const SUPPLY: u64 = PRICE * 10;
+
+fn discounted(price: u64) -> u64{
+    price * DISCOUNT_RATE as u64 / 100
+}
+
+#[tokio::test]
+async fn sellers_raise_the_price_and_the_buyer_tops_up(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_price(true, PRICE * 2).await.unwrap();
+    assert!(f.accept_price_sol(true).await.is_err());
+
+    f.accept_price_sol(false).await.unwrap();
+    let tx = f.transaction().await;
+    assert_eq!(tx.metadata.transaction_price, PRICE * 2);
+    assert_eq!(tx.proposed_price, 0);
+    assert_eq!(f.balance(f.escrow).await, PRICE * 2);
+}
+
+#[tokio::test]
+async fn buyers_can_only_lower_the_price(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.propose_price(false, PRICE * 2).await.is_err());
+    f.propose_price(false, PRICE / 2).await.unwrap();
+
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    f.accept_price_sol(true).await.unwrap();
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE / 2);
+    assert_eq!(f.balance(f.escrow).await, PRICE / 2);
+    assert!(f.accept_price_sol(false).await.is_err());
+}
+
+#[tokio::test]
+async fn discounted_proposals_are_compared_on_what_the_escrow_holds(){
+    let mut f = Fixture::new(Options{dispute_discounts: 1, ..Options::default()}).await;
+    f.open_sol(PRICE, true).await;
+    f.seller_accept().await;
+    f.fund_sol().await;
+    assert_eq!(f.balance(f.escrow).await, discounted(PRICE));
+
+    // listing the same price again is not an increase once the discount applies
+    assert!(f.propose_price(false, PRICE + 100).await.is_err());
+    f.propose_price(false, PRICE).await.unwrap();
+    assert_eq!(f.transaction().await.proposed_price, discounted(PRICE));
+
+    f.propose_price(true, PRICE * 2).await.unwrap();
+    f.accept_price_sol(false).await.unwrap();
+    assert_eq!(f.transaction().await.metadata.transaction_price, discounted(PRICE * 2));
+    assert_eq!(f.balance(f.escrow).await, discounted(PRICE * 2));
+}
+
+#[tokio::test]
+async fn spl_escrows_follow_the_price(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(SUPPLY).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+
+    f.propose_price(true, PRICE * 2).await.unwrap();
+    f.accept_price_spl(false).await.unwrap();
+    assert_eq!(f.token_balance(f.escrow).await, PRICE * 2);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - PRICE * 2);
+
+    f.propose_price(false, PRICE / 2).await.unwrap();
+    f.accept_price_spl(true).await.unwrap();
+    assert_eq!(f.token_balance(f.escrow).await, PRICE / 2);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - PRICE / 2);
+}
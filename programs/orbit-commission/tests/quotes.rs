@@ -0,0 +1,72 @@
+mod common;
+
+use anchor_lang::system_program;
+use common::*;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::signature::Signer;
+
+const QUOTED: u64 = 250_000_000;
+const SCOPE: [u8; 32] = [4u8; 32];
+const WINDOW: i64 = 60 * 60 * 24 * 14;
+
+#[tokio::test]
+async fn accepting_a_sol_quote_opens_funded(){
+    let mut f = Fixture::new(Options::default()).await;
+    let deadline = f.now().await + 60 * 60 * 24;
+    f.create_quote(QUOTED, system_program::ID, deadline, WINDOW, SCOPE).await.unwrap();
+    f.warp(60 * 60).await;
+    let accepted_at = f.now().await;
+    f.accept_quote_sol().await.unwrap();
+
+    let tx = f.transaction().await;
+    assert!(tx.metadata.transaction_state == TransactionState::BuyerFunded);
+    assert!(tx.metadata.funded);
+    assert_eq!(tx.metadata.transaction_price, QUOTED);
+    assert_eq!(tx.metadata.seller, SELLER_ID);
+    // the delivery clock starts at acceptance, the quote deadline only bounds acceptance
+    assert_eq!(tx.delivery_deadline, accepted_at + WINDOW);
+    assert_eq!(tx.scope_hash, SCOPE);
+    assert!(f.balance(f.escrow).await >= QUOTED);
+    // the quote is spent
+    assert!(f.ctx.banks_client.get_account(f.quote()).await.unwrap().is_none());
+
+    f.run_to_accepted().await;
+    f.close_sol().await;
+}
+
+#[tokio::test]
+async fn spl_quote_funds_in_the_quoted_mint(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(QUOTED * 2).await;
+    let deadline = f.now().await + 60 * 60 * 24;
+    let mint = f.mint.pubkey();
+
+    f.create_quote(QUOTED, mint, deadline, WINDOW, SCOPE).await.unwrap();
+    assert!(f.accept_quote_sol().await.is_err());
+    f.accept_quote_spl().await.unwrap();
+
+    assert_eq!(f.token_balance(f.escrow).await, QUOTED);
+    assert_eq!(f.transaction().await.metadata.currency, mint);
+}
+
+#[tokio::test]
+async fn quotes_need_a_future_deadline_and_can_be_withdrawn(){
+    let mut f = Fixture::new(Options::default()).await;
+    let now = f.now().await;
+    assert!(f.create_quote(QUOTED, system_program::ID, now - 1, WINDOW, SCOPE).await.is_err());
+    assert!(f.create_quote(QUOTED, system_program::ID, now + 60, 0, SCOPE).await.is_err());
+
+    f.create_quote(QUOTED, system_program::ID, now + 60, WINDOW, SCOPE).await.unwrap();
+    f.withdraw_quote().await.unwrap();
+    assert!(f.accept_quote_sol().await.is_err());
+    assert!(f.ctx.banks_client.get_account(f.quote()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn quotes_cant_be_accepted_after_their_deadline(){
+    let mut f = Fixture::new(Options::default()).await;
+    let deadline = f.now().await + 60;
+    f.create_quote(QUOTED, system_program::ID, deadline, WINDOW, SCOPE).await.unwrap();
+    f.warp(61).await;
+    assert!(f.accept_quote_sol().await.is_err());
+}
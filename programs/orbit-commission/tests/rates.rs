@@ -0,0 +1,122 @@
+mod common;
+
+use common::*;
+use orbit_commission::{CommissionTransaction, RateOfferStatus, MAX_RATE_OFFERS, MAX_RATE_ROUNDS};
+use solana_sdk::signature::Signer;
+
+/// what a transaction opened before basis points looked like: whole percentages only
+fn legacy(tx: &mut CommissionTransaction){
+    tx.metadata.rate = 95;
+    tx.preview_rate = 90;
+    tx.close_rate = 0;
+    tx.rate_bps = 0;
+    tx.preview_rate_bps = 0;
+    tx.close_rate_bps = 0;
+    tx.rates_in_bps = false;
+}
+
+#[tokio::test]
+async fn counter_offers_keep_the_trail(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_rate(true, 9000).await.unwrap();
+    f.propose_rate(false, 9200).await.unwrap();
+
+    let tx = f.transaction().await;
+    assert_eq!(tx.rate_rounds, 2);
+    assert!(tx.rate_offers[0].status == RateOfferStatus::Countered);
+    assert_eq!(tx.rate_offers[0].proposer, SELLER_ID);
+    assert!(tx.rate_offers[1].status == RateOfferStatus::Pending);
+    assert_eq!(tx.rate_offers[1].proposer, BUYER_ID);
+
+    f.accept_rate(true).await.unwrap();
+    let tx = f.transaction().await;
+    assert!(tx.rate_offers[1].status == RateOfferStatus::Accepted);
+    assert_eq!(tx.close_rate_bps, 9200);
+}
+
+#[tokio::test]
+async fn only_the_other_party_answers_an_offer(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_rate(true, 9000).await.unwrap();
+    assert!(f.accept_rate(true).await.is_err());
+    assert!(f.reject_rate(true).await.is_err());
+    assert!(f.withdraw_rate(false).await.is_err());
+
+    f.reject_rate(false).await.unwrap();
+    assert!(f.transaction().await.rate_offers[0].status == RateOfferStatus::Rejected);
+    assert!(f.accept_rate(false).await.is_err());
+}
+
+#[tokio::test]
+async fn proposers_can_withdraw(){
+    let mut f = Fixture::funded_sol().await;
+    f.propose_rate(true, 9000).await.unwrap();
+    f.withdraw_rate(true).await.unwrap();
+    assert!(f.transaction().await.rate_offers[0].status == RateOfferStatus::Withdrawn);
+    assert!(f.accept_rate(false).await.is_err());
+}
+
+#[tokio::test]
+async fn rates_stay_within_basis_points(){
+    let mut f = Fixture::funded_sol().await;
+    assert_eq!(f.transaction().await.rate_bps, 10000 - FEE_BPS);
+    assert!(f.propose_rate(true, 10001).await.is_err());
+    f.propose_rate(true, 10000).await.unwrap();
+
+    // fractions of a percent survive the round trip
+    f.propose_rate(false, 9750).await.unwrap();
+    f.accept_rate(true).await.unwrap();
+    let tx = f.transaction().await;
+    assert_eq!(tx.preview_rate_bps, 9750);
+    assert_eq!(tx.close_rate_bps, 9750);
+}
+
+#[tokio::test]
+async fn percentage_rates_migrate_once(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.migrate_rates().await.is_err());
+
+    f.edit_transaction(legacy).await;
+    f.migrate_rates().await.unwrap();
+    let tx = f.transaction().await;
+    assert!(tx.rates_in_bps);
+    assert_eq!((tx.rate_bps, tx.preview_rate_bps, tx.close_rate_bps), (9500, 9000, 0));
+    assert!(f.migrate_rates().await.is_err());
+}
+
+#[tokio::test]
+async fn percentage_rates_migrate_on_the_way_to_close(){
+    let mut f = Fixture::funded_sol().await;
+    f.edit_transaction(legacy).await;
+    f.run_to_accepted().await;
+    assert_eq!(f.transaction().await.close_rate_bps, 9500);
+
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.close_sol().await;
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, PRICE * 95 / 100);
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, PRICE * 5 / 100);
+}
+
+#[tokio::test]
+async fn rejecting_the_last_round_expires_it(){
+    let mut f = Fixture::funded_sol().await;
+    for round in 0..MAX_RATE_ROUNDS{
+        f.propose_rate(round % 2 == 0, 9000 + round as u16).await.unwrap();
+    }
+    let last = (MAX_RATE_ROUNDS as usize - 1) % MAX_RATE_OFFERS;
+    assert!(f.transaction().await.rate_offers[last].status == RateOfferStatus::Pending);
+
+    // there is no round left to counter with, the offer stays open until it is answered
+    assert!(f.propose_rate(true, 9500).await.is_err());
+    assert!(f.transaction().await.rate_offers[last].status == RateOfferStatus::Pending);
+
+    f.reject_rate(true).await.unwrap();
+    let tx = f.transaction().await;
+    assert_eq!(tx.rate_rounds, MAX_RATE_ROUNDS);
+    assert!(tx.rate_offers[last].status == RateOfferStatus::Expired);
+    assert!(tx.rate_offers.iter().all(|offer| offer.status != RateOfferStatus::Pending));
+
+    assert!(f.accept_rate(true).await.is_err());
+    assert!(f.propose_rate(false, 9600).await.is_err());
+}
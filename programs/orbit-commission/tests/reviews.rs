@@ -0,0 +1,44 @@
+mod common;
+
+use common::*;
+use orbit_commission::CommissionOutcome;
+
+#[tokio::test]
+async fn accepted_transactions_take_text_reviews(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_accepted().await;
+    assert!(f.leave_text_review(false, 5, [7; 32]).await.is_err());
+    f.close_sol().await;
+
+    let tx = f.transaction().await;
+    assert!(tx.outcome == CommissionOutcome::Accepted);
+    assert!(tx.review_deadline > 0);
+
+    f.leave_text_review(false, 5, [7; 32]).await.unwrap();
+    f.leave_text_review(true, 4, [9; 32]).await.unwrap();
+    let tx = f.transaction().await;
+    assert_eq!(tx.buyer_review_hash, [7; 32]);
+    assert_eq!(tx.seller_review_hash, [9; 32]);
+
+    assert!(f.leave_text_review(false, 1, [0; 32]).await.is_err());
+}
+
+#[tokio::test]
+async fn denied_transactions_cant_be_reviewed(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_denied().await;
+    f.close_sol().await;
+
+    assert!(f.transaction().await.outcome == CommissionOutcome::Denied);
+    assert!(f.leave_text_review(false, 1, [1; 32]).await.is_err());
+    assert!(f.leave_text_review(true, 1, [1; 32]).await.is_err());
+}
+
+#[tokio::test]
+async fn early_declines_cant_be_reviewed(){
+    let mut f = Fixture::funded_sol().await;
+    f.early_decline_sol().await;
+
+    assert!(f.transaction().await.outcome == CommissionOutcome::EarlyDeclined);
+    assert!(f.leave_text_review(false, 1, [1; 32]).await.is_err());
+}
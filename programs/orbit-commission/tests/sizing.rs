@@ -0,0 +1,76 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use common::*;
+use orbit_commission::{
+    transaction_space,
+    BASE_TRANSACTION_SPACE,
+    MAX_LINK_LEN
+};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn opens_at_base_size(){
+    let mut f = Fixture::funded_sol().await;
+    let tx = f.transaction().await;
+    assert_eq!(f.transaction_len().await, transaction_space(&tx).unwrap());
+    assert_eq!(f.transaction_len().await, BASE_TRANSACTION_SPACE);
+}
+
+#[tokio::test]
+async fn seller_pays_for_keys_and_links(){
+    let mut f = Fixture::funded_sol().await;
+    let before_len = f.transaction_len().await;
+    let seller = f.balance(f.seller.pubkey()).await;
+
+    let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    f.commit_init_keys(keys).await.unwrap();
+    f.commit_link(&"a".repeat(MAX_LINK_LEN)).await.unwrap();
+
+    let after_len = f.transaction_len().await;
+    assert_eq!(after_len, before_len + 4 * 32 + MAX_LINK_LEN);
+    let rent = Rent::default();
+    let paid = rent.minimum_balance(after_len) - rent.minimum_balance(before_len);
+    assert_eq!(seller - f.balance(f.seller.pubkey()).await, paid);
+    assert_eq!(f.transaction().await.seller_rent, paid);
+}
+
+#[tokio::test]
+async fn shorter_link_refunds_seller(){
+    let mut f = Fixture::funded_sol().await;
+    f.commit_link(&"a".repeat(MAX_LINK_LEN)).await.unwrap();
+    let long_len = f.transaction_len().await;
+    let seller = f.balance(f.seller.pubkey()).await;
+
+    f.commit_link("short").await.unwrap();
+    let short_len = f.transaction_len().await;
+    assert_eq!(short_len, long_len - MAX_LINK_LEN + 5);
+    let rent = Rent::default();
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, rent.minimum_balance(long_len) - rent.minimum_balance(short_len));
+    assert_eq!(f.transaction().await.seller_rent, rent.minimum_balance(short_len) - rent.minimum_balance(BASE_TRANSACTION_SPACE));
+}
+
+#[tokio::test]
+async fn closing_the_account_returns_rent_to_whoever_paid_it(){
+    let mut f = Fixture::funded_sol().await;
+    f.commit_link(&"a".repeat(MAX_LINK_LEN)).await.unwrap();
+    let seller_rent = f.transaction().await.seller_rent;
+    assert!(seller_rent > 0);
+    f.run_to_accepted().await;
+    f.close_sol().await;
+
+    let lamports = f.balance(f.commission_tx).await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    f.close_transaction_account(false).await.unwrap();
+
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, seller_rent);
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, lamports - seller_rent);
+}
+
+#[tokio::test]
+async fn rejects_oversized_link(){
+    let mut f = Fixture::funded_sol().await;
+    assert!(f.commit_link(&"a".repeat(MAX_LINK_LEN + 1)).await.is_err());
+    assert_eq!(f.transaction_len().await, BASE_TRANSACTION_SPACE);
+}
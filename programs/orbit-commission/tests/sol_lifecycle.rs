@@ -0,0 +1,132 @@
+mod common;
+
+use common::*;
+use orbit_commission::BuyerDecisionState;
+use orbit_transaction::transaction_struct::TransactionState;
+use solana_sdk::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+    signature::Signer
+};
+
+#[tokio::test]
+async fn accept_pays_seller_and_treasury(){
+    let mut f = Fixture::funded_sol().await;
+    assert_eq!(f.balance(f.escrow).await, PRICE);
+
+    f.run_to_accepted().await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.close_sol().await;
+
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, PRICE - fee);
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, fee);
+    assert_eq!(f.balance(f.buyer.pubkey()).await, buyer);
+    assert_eq!(f.balance(f.escrow).await, 0);
+
+    let tx = f.transaction().await;
+    assert!(tx.metadata.transaction_state == TransactionState::Closed);
+    assert!(tx.final_decision == BuyerDecisionState::Accept);
+}
+
+#[tokio::test]
+async fn deny_refunds_buyer(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_denied().await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.close_sol().await;
+
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE);
+    assert_eq!(f.balance(f.seller.pubkey()).await, seller);
+    assert_eq!(f.balance(f.treasury.pubkey()).await, treasury);
+    assert_eq!(f.balance(f.escrow).await, 0);
+}
+
+#[tokio::test]
+async fn discount_lowers_price_and_skips_fee(){
+    let mut f = Fixture::funded_sol_with(Options{dispute_discounts: 1, ..Options::default()}, true).await;
+    let discounted = PRICE * DISCOUNT_RATE as u64 / 100;
+    assert_eq!(f.balance(f.escrow).await, discounted);
+    assert_eq!(f.transaction().await.metadata.rate, 100);
+
+    f.run_to_accepted().await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    f.close_sol().await;
+
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, discounted);
+    assert_eq!(f.balance(f.treasury.pubkey()).await, treasury);
+    assert_eq!(f.balance(f.escrow).await, 0);
+}
+
+#[tokio::test]
+async fn discount_ignored_without_credits(){
+    let mut f = Fixture::funded_sol_with(Options::default(), true).await;
+    assert_eq!(f.balance(f.escrow).await, PRICE);
+    assert_eq!(f.transaction().await.rate_bps, 10000 - FEE_BPS);
+}
+
+#[tokio::test]
+async fn reflink_splits_fee(){
+    let mut f = Fixture::funded_sol_with(Options{reflink: true, ..Options::default()}, false).await;
+    f.run_to_accepted().await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    let reflink = f.balance(f.reflink_owner.pubkey()).await;
+    f.close_sol().await;
+
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    let referral = PRICE * REFERRAL_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, PRICE - fee);
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, referral);
+    assert_eq!(f.balance(f.reflink_owner.pubkey()).await - reflink, referral);
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, fee - 2 * referral);
+    assert_eq!(f.balance(f.escrow).await, 0);
+}
+
+#[tokio::test]
+async fn missing_reflink_falls_back_to_treasury(){
+    let mut f = Fixture::funded_sol_with(Options{reflink: true, ..Options::default()}, false).await;
+    f.run_to_accepted().await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let treasury = f.balance(f.treasury.pubkey()).await;
+    let reflink = f.balance(f.reflink_owner.pubkey()).await;
+    f.try_close_sol(vec![]).await.unwrap();
+
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.treasury.pubkey()).await - treasury, fee);
+    assert_eq!(f.balance(f.buyer.pubkey()).await, buyer);
+    assert_eq!(f.balance(f.reflink_owner.pubkey()).await, reflink);
+}
+
+#[tokio::test]
+async fn reflink_payee_must_be_the_owner(){
+    let mut f = Fixture::funded_sol_with(Options{reflink: true, ..Options::default()}, false).await;
+    f.run_to_accepted().await;
+    let mut remaining = f.reflink_metas();
+    remaining[1] = AccountMeta::new(f.seller.pubkey(), false);
+    assert!(f.try_close_sol(remaining).await.is_err());
+
+    let remaining = vec![
+        AccountMeta::new(Pubkey::new_unique(), false),
+        AccountMeta::new(f.reflink_owner.pubkey(), false)
+    ];
+    assert!(f.try_close_sol(remaining).await.is_err());
+}
+
+#[tokio::test]
+async fn early_decline_refunds_buyer(){
+    let mut f = Fixture::funded_sol().await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    let seller = f.balance(f.seller.pubkey()).await;
+    f.early_decline_sol().await;
+
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, PRICE);
+    assert_eq!(f.balance(f.seller.pubkey()).await, seller);
+    assert_eq!(f.balance(f.escrow).await, 0);
+}
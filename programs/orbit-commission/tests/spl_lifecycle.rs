@@ -0,0 +1,120 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+
+const SUPPLY: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn accept_pays_seller_and_treasury(){
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    assert_eq!(f.token_balance(f.escrow).await, PRICE);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - PRICE);
+
+    f.run_to_accepted().await;
+    f.close_spl().await;
+
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, PRICE - fee);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, fee);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - PRICE);
+}
+
+#[tokio::test]
+async fn close_returns_escrow_rent_to_buyer(){
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    f.run_to_accepted().await;
+    let rent = f.balance(f.escrow).await;
+    let buyer = f.balance(f.buyer.pubkey()).await;
+    f.close_spl().await;
+
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_none());
+    assert_eq!(f.balance(f.buyer.pubkey()).await - buyer, rent);
+}
+
+#[tokio::test]
+async fn deny_refunds_buyer(){
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    f.run_to_denied().await;
+    f.close_spl().await;
+
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY);
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, 0);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, 0);
+}
+
+#[tokio::test]
+async fn discount_lowers_price_and_skips_fee(){
+    let mut f = Fixture::funded_spl_with(Options{dispute_discounts: 1, ..Options::default()}, true, SUPPLY).await;
+    let discounted = PRICE * DISCOUNT_RATE as u64 / 100;
+    assert_eq!(f.token_balance(f.escrow).await, discounted);
+
+    f.run_to_accepted().await;
+    f.close_spl().await;
+
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, discounted);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, 0);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY - discounted);
+}
+
+#[tokio::test]
+async fn early_decline_refunds_buyer(){
+    let mut f = Fixture::funded_spl_with(Options::default(), false, SUPPLY).await;
+    f.early_decline_spl().await;
+
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, SUPPLY);
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, 0);
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_none());
+}
+
+//////////////////////////////////////////////////////////////////////////
+/// TOKEN-2022
+
+const TRANSFER_FEE_BPS: u16 = 100;
+const MAX_TRANSFER_FEE: u64 = u64::MAX;
+
+fn withheld(amount: u64) -> u64{
+    ((amount as u128 * TRANSFER_FEE_BPS as u128 + 9999) / 10000) as u64
+}
+
+#[tokio::test]
+async fn token_2022_accept_pays_seller_and_treasury(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens_with(SUPPLY, spl_token_2022::ID, None).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+    f.run_to_accepted().await;
+    f.close_spl().await;
+
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, PRICE - fee);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, fee);
+    assert!(f.ctx.banks_client.get_account(f.escrow).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn transfer_fee_mint_funds_full_price(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens_with(SUPPLY, spl_token_2022::ID, Some((TRANSFER_FEE_BPS, MAX_TRANSFER_FEE))).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+
+    // the buyer covers the transfer fee in and a reserve for the fees on the way out:
+    // ceil(PRICE * 1% / 99%) plus a unit for each of the 36 possible payouts
+    let escrowed = f.token_balance(f.escrow).await;
+    assert_eq!(escrowed, PRICE + 1_010_102 + 36);
+    let sent = SUPPLY - f.token_balance(f.buyer_ata.pubkey()).await;
+    assert_eq!(sent - withheld(sent), escrowed);
+
+    f.run_to_accepted().await;
+    f.close_spl().await;
+
+    // payouts are grossed up out of the reserve so everyone is credited their full share
+    let fee = PRICE * FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.escrow).await, 0);
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, PRICE - fee);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, fee);
+    assert!(f.token_balance(f.buyer_ata.pubkey()).await > SUPPLY - sent);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+
+const TIP: u64 = PRICE / 10;
+
+#[tokio::test]
+async fn buyer_tips_after_accepting_until_the_account_closes(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_accepted().await;
+
+    let seller = f.balance(f.seller.pubkey()).await;
+    let multisig = f.balance(f.multisig.pubkey()).await;
+    let len = f.transaction_len().await;
+    f.tip_sol(TIP).await.unwrap();
+
+    let cut = TIP * TIP_FEE_BPS as u64 / 10000;
+    assert_eq!(f.balance(f.seller.pubkey()).await - seller, TIP - cut);
+    assert_eq!(f.balance(f.multisig.pubkey()).await - multisig, cut);
+    assert_eq!(f.transaction_len().await, len);
+
+    f.close_sol().await;
+    f.tip_sol(TIP).await.unwrap();
+    assert_eq!(f.transaction().await.tipped, 2 * TIP);
+}
+
+#[tokio::test]
+async fn tips_wait_for_the_buyer_decision(){
+    let mut f = Fixture::funded_sol().await;
+    f.ship().await;
+    assert!(f.tip_sol(TIP).await.is_err());
+}
+
+#[tokio::test]
+async fn denied_work_cant_be_tipped(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_denied().await;
+    assert!(f.tip_sol(TIP).await.is_err());
+}
+
+#[tokio::test]
+async fn early_declines_cant_be_tipped(){
+    let mut f = Fixture::funded_sol().await;
+    f.early_decline_sol().await;
+    assert!(f.tip_sol(TIP).await.is_err());
+}
+
+#[tokio::test]
+async fn zero_tips_are_rejected(){
+    let mut f = Fixture::funded_sol().await;
+    f.run_to_accepted().await;
+    assert!(f.tip_sol(0).await.is_err());
+}
+
+#[tokio::test]
+async fn spl_tips_use_the_transaction_mint(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens(PRICE + TIP).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+    f.run_to_accepted().await;
+
+    assert!(f.tip_sol(TIP).await.is_err());
+    f.tip_spl(TIP).await.unwrap();
+
+    let cut = TIP * TIP_FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, TIP - cut);
+    assert_eq!(f.token_balance(f.multisig_ata.pubkey()).await, cut);
+    assert_eq!(f.token_balance(f.treasury_ata.pubkey()).await, 0);
+    assert_eq!(f.token_balance(f.buyer_ata.pubkey()).await, 0);
+    assert_eq!(f.transaction().await.tipped, TIP);
+}
+
+#[tokio::test]
+async fn spl_tips_cover_the_transfer_fee(){
+    let mut f = Fixture::new(Options::default()).await;
+    f.setup_tokens_with(PRICE * 3, spl_token_2022::ID, Some((100, u64::MAX))).await;
+    f.open_spl(PRICE, false).await;
+    f.seller_accept().await;
+    f.fund_spl().await;
+    f.run_to_accepted().await;
+
+    let buyer = f.token_balance(f.buyer_ata.pubkey()).await;
+    f.tip_spl(TIP).await.unwrap();
+
+    // the buyer pays the 1% fee on top, seller and multisig are credited the full split
+    let cut = TIP * TIP_FEE_BPS as u64 / 10000;
+    assert_eq!(f.token_balance(f.seller_ata.pubkey()).await, TIP - cut);
+    assert_eq!(f.token_balance(f.multisig_ata.pubkey()).await, cut);
+    assert!(buyer - f.token_balance(f.buyer_ata.pubkey()).await > TIP);
+    assert_eq!(f.transaction().await.tipped, TIP);
+}